@@ -0,0 +1,118 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Parsing Throughput
+ *      cargo bench --bench parsing
+ * 
+ *      Reports MB/s for each stage of loading a large scanned mesh.
+ *      The mesh is generated, unless WFOBJ_BENCH_FILE points at a real one.
+ *      WFOBJ_BENCH_SIZE sets the width of the generated grid. (default 600)
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::env;
+use std::fmt::Write;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use wfobj_rs::{utility, interpret_parallel, ObjInterpreter, ObjLine, ObjLineRef, ObjParser, ParseOptions, Vertex};
+
+const RUNS: usize = 5;
+
+// Grid of noisy heights with texture coordinates and normals, triangulated. Similar to a scanned surface.
+fn scanned_mesh(width: usize) -> String {
+    let mut data = String::new();
+    let mut seed: u32 = 0x2545_f491;
+    let mut noise = || {
+        seed ^= seed << 13;
+        seed ^= seed >> 17;
+        seed ^= seed << 5;
+        seed as f32 / u32::MAX as f32
+    };
+
+    for y in 0..width {
+        for x in 0..width {
+            let (u, v) = (x as f32 / width as f32, y as f32 / width as f32);
+            writeln!(data, "v {:.6} {:.6} {:.6}", u * 10.0, v * 10.0, noise() * 0.25).unwrap();
+            writeln!(data, "vt {u:.6} {v:.6}").unwrap();
+            writeln!(data, "vn {:.6} {:.6} {:.6}", noise() * 0.1, noise() * 0.1, 0.994987).unwrap();
+        }
+    }
+
+    for y in 0..width - 1 {
+        for x in 0..width - 1 {
+            let a = y * width + x + 1;
+            let (b, c, d) = (a + 1, a + width, a + width + 1);
+            writeln!(data, "f {a}/{a}/{a} {b}/{b}/{b} {d}/{d}/{d}").unwrap();
+            writeln!(data, "f {a}/{a}/{a} {d}/{d}/{d} {c}/{c}/{c}").unwrap();
+        }
+    }
+    data
+}
+
+// Best of several runs
+fn measure<T>(name: &str, bytes: usize, mut stage: impl FnMut() -> T) {
+    let mut best = Duration::MAX;
+    for _ in 0..RUNS {
+        let start = Instant::now();
+        black_box(stage());
+        best = best.min(start.elapsed());
+    }
+    println!("{name:<32} {:>10.1} MB/s  {:>10.2?}", bytes as f64 / best.as_secs_f64() / 1e6, best);
+}
+
+fn main() {
+    let data = match env::var("WFOBJ_BENCH_FILE") {
+        Ok(path) => std::fs::read_to_string(path).expect("couldn't read WFOBJ_BENCH_FILE"),
+        Err(_)   => scanned_mesh(env::var("WFOBJ_BENCH_SIZE").ok().and_then(|size| size.parse().ok()).unwrap_or(600))
+    };
+    let bytes = data.len();
+
+    // Only the vertex lines, for the vertex data stages
+    let vertex_lines: Vec<&str> = data.lines().filter(|line| line.starts_with("v ")).collect();
+    let vertex_bytes: usize = vertex_lines.iter().map(|line| line.len() + 1).sum();
+    let tokens: Vec<&str> = vertex_lines.iter().flat_map(|line| line.split_ascii_whitespace().skip(1)).collect();
+    let token_bytes: usize = tokens.iter().map(|token| token.len() + 1).sum();
+
+    println!("{:.1} MB, {} lines", bytes as f64 / 1e6, data.lines().count());
+
+    measure("tokenize (ObjLineRef)", bytes, || {
+        let mut parser = ObjParser::from_str(&data);
+        let mut parameters = 0usize;
+        while let Some(line) = parser.get_line_ref() {
+            parameters += line.parameters().count();
+        }
+        parameters
+    });
+
+    measure("tokenize (ObjLine)", bytes, || {
+        ObjParser::from_str(&data).map(|line| line.unwrap().parameters.len()).sum::<usize>()
+    });
+
+    measure("floats (str::parse)", token_bytes, || {
+        tokens.iter().map(|token| token.parse::<f32>().unwrap()).sum::<f32>()
+    });
+
+    measure("floats (fast path)", token_bytes, || {
+        tokens.iter().map(|token| utility::parse_f32(token).unwrap()).sum::<f32>()
+    });
+
+    measure("vertices (from ObjLine)", vertex_bytes, || {
+        vertex_lines.iter().map(|line| Vertex::from(&ObjLine::from(line).parameters).unwrap().x).sum::<f32>()
+    });
+
+    measure("vertices (fast path)", vertex_bytes, || {
+        vertex_lines.iter().map(|line| Vertex::parse(ObjLineRef::from(line).parameters).unwrap().x).sum::<f32>()
+    });
+
+    measure("interpret", bytes, || {
+        ObjInterpreter::new(ObjParser::from_str(&data)).interpret().unwrap().elements.len()
+    });
+
+    let threads = std::thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1);
+    measure(&format!("interpret ({threads} threads)"), bytes, || {
+        interpret_parallel(&data, threads, ParseOptions::default()).unwrap().elements.len()
+    });
+}
@@ -0,0 +1,111 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ *
+ * Diagnostics
+ *      Every problem found while loading a file, rather than only the first.
+ *      Bad statements are skipped, and loading carries on with the next line.
+ *      The model is whatever could be read around them.
+ *
+ *      Each diagnostic has a severity, the span of the statement, and a code.
+ *      Codes are stable, so they can be filtered or counted. (ex: "unknown-keyword")
+ *          - Errors are statements that were skipped
+ *          - Warnings are deviations from the specification the parse options recovered from
+ *
+ * ------------------------------------------------------------------------------------*/
+
+use std::fmt;
+
+use crate::interpreter::InterpreterError;
+use crate::options::Deviation;
+use crate::span::{Span, Spanned};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error
+}
+
+// What went wrong. Keeps the underlying error for details
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiagnosticCode {
+    Interpreter(InterpreterError),
+    Deviation(Deviation)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub span:     Span,
+    pub code:     DiagnosticCode
+}
+
+impl Diagnostic {
+    pub fn error(error: Spanned<InterpreterError>) -> Diagnostic {
+        Diagnostic { severity: Severity::Error, span: error.span, code: DiagnosticCode::Interpreter(error.error) }
+    }
+
+    pub fn warning(deviation: Spanned<Deviation>) -> Diagnostic {
+        Diagnostic { severity: Severity::Warning, span: deviation.span, code: DiagnosticCode::Deviation(deviation.error) }
+    }
+}
+
+impl DiagnosticCode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DiagnosticCode::Interpreter(error) => match error {
+                InterpreterError::VertexData(_)               => "invalid-vertex-data",
+                InterpreterError::FreeFormDefinition(_)       => "invalid-free-form-attribute",
+                InterpreterError::FreeFormValidation(_)       => "invalid-free-form-definition",
+                InterpreterError::PolygonalElement(_)         => "invalid-element",
+                InterpreterError::FreeFormElement(_)          => "invalid-free-form-element",
+                InterpreterError::BodyStatement(_)            => "invalid-body-statement",
+                InterpreterError::Connection(_)               => "invalid-connection",
+                InterpreterError::Grouping(_)                 => "invalid-grouping",
+                InterpreterError::RenderState(_)              => "invalid-render-attribute",
+                InterpreterError::Index(_)                    => "invalid-reference",
+                InterpreterError::UnknownKeyword              => "unknown-keyword",
+                InterpreterError::UnexpectedKeyword           => "unexpected-keyword",
+                InterpreterError::BodyStatementOutsideElement => "body-statement-outside-element",
+                InterpreterError::UnexpectedEnd               => "unexpected-end",
+                InterpreterError::MissingEnd                  => "missing-end",
+                InterpreterError::IO(_)                       => "io"
+            },
+            DiagnosticCode::Deviation(deviation) => match deviation {
+                Deviation::TrailingSlash                      => "trailing-slash",
+                Deviation::UnknownKeyword                     => "skipped-unknown-keyword",
+                Deviation::VertexColor                        => "vertex-color",
+                Deviation::ObjectNameWithSpaces               => "object-name-with-spaces",
+                Deviation::Latin1Text                         => "latin1-text",
+                Deviation::InvalidDefinition                  => "invalid-free-form-definition"
+            }
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Severity::Warning => write!(f, "warning"),
+            Severity::Error   => write!(f, "error")
+        }
+    }
+}
+
+impl fmt::Display for DiagnosticCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DiagnosticCode::Interpreter(InterpreterError::IO(error)) => write!(f, "line couldn't be read: {error}"),
+            DiagnosticCode::Interpreter(error)                       => write!(f, "{error}"),
+            DiagnosticCode::Deviation(deviation)                     => write!(f, "{deviation}")
+        }
+    }
+}
+
+// ex: "line 4, column 1: error[unknown-keyword]: keyword isn't part of the OBJ specification"
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}[{}]: {}", self.span, self.severity, self.code.as_str(), self.code)
+    }
+}
@@ -0,0 +1,86 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ *
+ * OBJ Elements
+ *      Elements are the statements that describe geometry using the vertex data.
+ *      Polygonal: Points, Lines, Faces
+ *      Free-form: Curves, 2D Curves, Surfaces
+ *
+ *      Each element keeps a reference to the state that was active when it was read.
+ *      Elements defined under the same state share the same state object.
+ *
+ * ------------------------------------------------------------------------------------*/
+
+use std::rc::Rc;
+
+use crate::freeform_geometry::freeform_elements::{Curve, Curve2D, Surface};
+use crate::polygonal_geometry::polygonal_elements::{Point, Line, Face};
+use crate::keywords;
+use crate::grouping::Grouping;
+use crate::render_state::RenderState;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ElementType {
+    Point,
+    Line,
+    Face,
+    Curve,
+    Curve2D,
+    Surface
+}
+
+impl ElementType {
+    pub fn from_keyword(keyword: &str) -> Option<ElementType> {
+        match keyword {
+            keywords::POINT    => Some(ElementType::Point),
+            keywords::LINE     => Some(ElementType::Line),
+            keywords::FACE     => Some(ElementType::Face),
+            keywords::CURVE    => Some(ElementType::Curve),
+            keywords::CURVE_2D => Some(ElementType::Curve2D),
+            keywords::SURFACE  => Some(ElementType::Surface),
+            _                  => None
+        }
+    }
+
+    pub fn is_free_form(&self) -> bool {
+        matches!(self, ElementType::Curve | ElementType::Curve2D | ElementType::Surface)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    Point(Point),
+    Line(Line),
+    Face(Face),
+    Curve(Curve),
+    Curve2D(Curve2D),
+    Surface(Surface)
+}
+
+impl Geometry {
+    pub fn element_type(&self) -> ElementType {
+        match self {
+            Geometry::Point(_)   => ElementType::Point,
+            Geometry::Line(_)    => ElementType::Line,
+            Geometry::Face(_)    => ElementType::Face,
+            Geometry::Curve(_)   => ElementType::Curve,
+            Geometry::Curve2D(_) => ElementType::Curve2D,
+            Geometry::Surface(_) => ElementType::Surface
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Element {
+    pub geometry:     Geometry,
+    pub grouping:     Rc<Grouping>,             // Active groups, object, smoothing and merging group
+    pub render_state: Rc<RenderState>           // Active display/render attributes
+}
+
+impl Element {
+    pub fn element_type(&self) -> ElementType {
+        self.geometry.element_type()
+    }
+}
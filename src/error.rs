@@ -0,0 +1,492 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Crate Errors
+ *      Each module reports its own error type. ObjError unifies them so "?" works
+ *      across the whole crate. Every error implements std::error::Error and Display.
+ * 
+ *      Errors caused by a statement keep the span of that statement.
+ *      Errors wrapping another error expose it through source()
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::fmt;
+use std::sync::Arc;
+use std::error::Error;
+
+use crate::span::{Span, Spanned};
+use crate::utility::UtilityError;
+use crate::index::IndexError;
+use crate::vertex_buffer::VertexBufferError;
+use crate::interpreter::InterpreterError;
+use crate::grouping::GroupingError;
+use crate::render_state::RenderStateError;
+use crate::polygonal_geometry::index_triplet::IndexTripletError;
+use crate::polygonal_geometry::polygonal_elements::PolygonalElementError;
+use crate::freeform_geometry::freeform_definition::{FreeFormDefinitionError, FreeFormValidationExceptions};
+use crate::freeform_geometry::freeform_types::FreeFormTypesErrors;
+use crate::freeform_geometry::freeform_elements::FreeFormElementError;
+use crate::freeform_geometry::body_statements::BodyStatementError;
+use crate::freeform_geometry::connection::ConnectionError;
+use crate::freeform_geometry::evaluation::EvaluationError;
+use crate::freeform_geometry::uv_pair::UVPairError;
+use crate::freeform_geometry::basis_matrix::basis_matrix_attributes::BasisMatrixAttributesError;
+use crate::freeform_geometry::basis_matrix::matrix::MatrixError;
+use crate::mtl::material::MaterialError;
+
+#[derive(Debug)]
+pub enum ObjError {
+    IO(std::io::Error),
+    Spanned(Span, Box<ObjError>),                       // Error caused by the statement at the span
+    Interpreter(InterpreterError),
+    VertexBuffer(VertexBufferError),
+    Index(IndexError),
+    IndexTriplet(IndexTripletError),
+    PolygonalElement(PolygonalElementError),
+    FreeFormDefinition(FreeFormDefinitionError),
+    FreeFormValidation(FreeFormValidationExceptions),
+    FreeFormElement(FreeFormElementError),
+    BodyStatement(BodyStatementError),
+    Connection(ConnectionError),
+    Evaluation(EvaluationError),
+    FreeFormType(FreeFormTypesErrors),
+    UVPair(UVPairError),
+    BasisMatrixAttributes(BasisMatrixAttributesError),
+    Matrix(MatrixError),
+    Grouping(GroupingError),
+    RenderState(RenderStateError),
+    Utility(UtilityError),
+    Material(MaterialError)
+}
+
+impl ObjError {
+    // Span of the statement that caused the error, if known
+    pub fn span(&self) -> Option<Span> {
+        match self {
+            ObjError::Spanned(span, _) => Some(*span),
+            _                          => None
+        }
+    }
+
+    // The error without its span
+    pub fn inner(&self) -> &ObjError {
+        match self {
+            ObjError::Spanned(_, error) => error.inner(),
+            error                       => error
+        }
+    }
+
+    fn as_error(&self) -> &(dyn Error + 'static) {
+        match self {
+            ObjError::IO(error)                    => error,
+            ObjError::Spanned(_, error)            => error.as_error(),
+            ObjError::Interpreter(error)           => error,
+            ObjError::VertexBuffer(error)          => error,
+            ObjError::Index(error)                 => error,
+            ObjError::IndexTriplet(error)          => error,
+            ObjError::PolygonalElement(error)      => error,
+            ObjError::FreeFormDefinition(error)    => error,
+            ObjError::FreeFormValidation(error)    => error,
+            ObjError::FreeFormElement(error)       => error,
+            ObjError::BodyStatement(error)         => error,
+            ObjError::Connection(error)            => error,
+            ObjError::Evaluation(error)            => error,
+            ObjError::FreeFormType(error)          => error,
+            ObjError::UVPair(error)                => error,
+            ObjError::BasisMatrixAttributes(error) => error,
+            ObjError::Matrix(error)                => error,
+            ObjError::Grouping(error)              => error,
+            ObjError::RenderState(error)           => error,
+            ObjError::Utility(error)               => error,
+            ObjError::Material(error)              => error
+        }
+    }
+}
+
+impl fmt::Display for ObjError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ObjError::Spanned(span, error) => write!(f, "{span}: {error}"),
+            error                          => write!(f, "{}", error.as_error())
+        }
+    }
+}
+
+// ObjError is transparent, the source is the source of the wrapped error
+impl Error for ObjError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.as_error().source()
+    }
+}
+
+impl<E: Into<ObjError>> From<Spanned<E>> for ObjError {
+    fn from(error: Spanned<E>) -> Self {
+        ObjError::Spanned(error.span, Box::new(error.error.into()))
+    }
+}
+
+macro_rules! obj_error_from {
+    ( $( $error:ty => $variant:ident ),* ) => {
+        $(
+            impl From<$error> for ObjError {
+                fn from(error: $error) -> Self {
+                    ObjError::$variant(error)
+                }
+            }
+        )*
+    };
+}
+
+obj_error_from!(
+    std::io::Error               => IO,
+    InterpreterError             => Interpreter,
+    VertexBufferError            => VertexBuffer,
+    IndexError                   => Index,
+    IndexTripletError            => IndexTriplet,
+    PolygonalElementError        => PolygonalElement,
+    FreeFormDefinitionError      => FreeFormDefinition,
+    FreeFormValidationExceptions => FreeFormValidation,
+    FreeFormElementError         => FreeFormElement,
+    BodyStatementError           => BodyStatement,
+    ConnectionError              => Connection,
+    EvaluationError              => Evaluation,
+    FreeFormTypesErrors          => FreeFormType,
+    UVPairError                  => UVPair,
+    BasisMatrixAttributesError   => BasisMatrixAttributes,
+    MatrixError                  => Matrix,
+    GroupingError                => Grouping,
+    RenderStateError             => RenderState,
+    UtilityError                 => Utility,
+    MaterialError                => Material
+);
+
+
+/* --------------------------------------------------------------------------------------
+ * IO
+ * ------------------------------------------------------------------------------------*/
+
+// A read error kept by an error that needs to be cloned and compared. Errors of the same kind are equal
+#[derive(Debug, Clone)]
+pub struct IoError(Arc<std::io::Error>);
+
+impl IoError {
+    pub fn kind(&self) -> std::io::ErrorKind {
+        self.0.kind()
+    }
+
+    pub fn get_ref(&self) -> &std::io::Error {
+        &self.0
+    }
+}
+
+impl From<std::io::Error> for IoError {
+    fn from(error: std::io::Error) -> Self {
+        IoError(Arc::new(error))
+    }
+}
+
+impl From<std::io::ErrorKind> for IoError {
+    fn from(kind: std::io::ErrorKind) -> Self {
+        IoError(Arc::new(kind.into()))
+    }
+}
+
+impl PartialEq for IoError {
+    fn eq(&self, other: &Self) -> bool {
+        self.kind() == other.kind()
+    }
+}
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+
+/* --------------------------------------------------------------------------------------
+ * Spans
+ * ------------------------------------------------------------------------------------*/
+
+impl fmt::Display for Span {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_multiline() {
+            write!(f, "lines {}-{}, column {}", self.start_line, self.end_line, self.start_column)
+        } else {
+            write!(f, "line {}, column {}", self.start_line, self.start_column)
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for Spanned<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.span, self.error)
+    }
+}
+
+impl<E: Error> Error for Spanned<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        self.error.source()
+    }
+}
+
+
+/* --------------------------------------------------------------------------------------
+ * Module Errors
+ * ------------------------------------------------------------------------------------*/
+
+// Display messages for errors. Errors without a source only need their messages.
+macro_rules! error_messages {
+    ( $error:ty { $( $pattern:pat => $message:expr ),* $(,)? } ) => {
+        impl fmt::Display for $error {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                match self {
+                    $( $pattern => write!(f, "{}", $message), )*
+                }
+            }
+        }
+    };
+}
+
+error_messages!(InterpreterError {
+    InterpreterError::VertexData(_)               => "invalid vertex data",
+    InterpreterError::FreeFormDefinition(_)       => "invalid free-form attribute",
+    InterpreterError::FreeFormValidation(_)       => "free-form definition doesn't validate",
+    InterpreterError::PolygonalElement(_)         => "invalid element",
+    InterpreterError::FreeFormElement(_)          => "invalid free-form element",
+    InterpreterError::BodyStatement(_)            => "invalid body statement",
+    InterpreterError::Connection(_)               => "invalid connection",
+    InterpreterError::Grouping(_)                 => "invalid grouping statement",
+    InterpreterError::RenderState(_)              => "invalid display/render attribute",
+    InterpreterError::Index(_)                    => "invalid element reference",
+    InterpreterError::UnknownKeyword              => "keyword isn't part of the OBJ specification",
+    InterpreterError::UnexpectedKeyword           => "keyword can't start a statement",
+    InterpreterError::BodyStatementOutsideElement => "body statement outside of a free-form element",
+    InterpreterError::UnexpectedEnd               => "\"end\" without a free-form element",
+    InterpreterError::MissingEnd                  => "free-form element is missing its \"end\" statement",
+    InterpreterError::IO(_)                       => "line couldn't be read"
+});
+
+impl Error for InterpreterError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            InterpreterError::VertexData(error)         => Some(error),
+            InterpreterError::FreeFormDefinition(error) => Some(error),
+            InterpreterError::FreeFormValidation(error) => Some(error),
+            InterpreterError::PolygonalElement(error)   => Some(error),
+            InterpreterError::FreeFormElement(error)    => Some(error),
+            InterpreterError::BodyStatement(error)      => Some(error),
+            InterpreterError::Connection(error)         => Some(error),
+            InterpreterError::Grouping(error)           => Some(error),
+            InterpreterError::RenderState(error)        => Some(error),
+            InterpreterError::Index(error)              => Some(error),
+            InterpreterError::IO(error)                 => Some(error.get_ref()),
+            _                                           => None
+        }
+    }
+}
+
+error_messages!(VertexBufferError {
+    VertexBufferError::InterpreterError => "vertex data has the wrong number of components, or a component isn't a number",
+    VertexBufferError::BoundsException  => "index is outside of the vertex buffer"
+});
+impl Error for VertexBufferError {}
+
+error_messages!(IndexError {
+    IndexError::IndexIsZero => "index can't be zero",
+    IndexError::OutOfBounds => "index is outside of the buffer it references"
+});
+impl Error for IndexError {}
+
+error_messages!(IndexTripletError {
+    IndexTripletError::InvalidFormat    => "reference must be one of v, v/vt, v//vn, or v/vt/vn",
+    IndexTripletError::InvalidIndex(_)  => "invalid index in reference",
+    IndexTripletError::ConversionError  => "reference component isn't an integer"
+});
+
+impl Error for IndexTripletError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            IndexTripletError::InvalidIndex(error) => Some(error),
+            _                                      => None
+        }
+    }
+}
+
+error_messages!(PolygonalElementError {
+    PolygonalElementError::InvalidReference(_)    => "invalid vertex reference",
+    PolygonalElementError::MixedReferenceFormats  => "every reference in an element must use the same form",
+    PolygonalElementError::InvalidReferenceFormat => "reference form isn't allowed for this element",
+    PolygonalElementError::NotEnoughReferences    => "not enough references for this element"
+});
+
+impl Error for PolygonalElementError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            PolygonalElementError::InvalidReference(error) => Some(error),
+            _                                              => None
+        }
+    }
+}
+
+error_messages!(FreeFormDefinitionError {
+    FreeFormDefinitionError::MissingKeyword       => "missing keyword",
+    FreeFormDefinitionError::CurveSurfaceMismatch => "curve and surface attributes are mixed",
+    FreeFormDefinitionError::InvalidKeyword       => "invalid keyword",
+    FreeFormDefinitionError::InvalidFormType      => "attribute isn't valid for the current curve/surface type",
+    FreeFormDefinitionError::InvalidParameters    => "invalid parameters",
+    FreeFormDefinitionError::InvalidFreeFormType  => "unknown curve/surface type",
+    FreeFormDefinitionError::InvalidBufferSize    => "wrong number of parameters",
+    FreeFormDefinitionError::MalformedDefinition  => "free-form definition requires a type and a degree",
+    FreeFormDefinitionError::UnknownError         => "unknown error"
+});
+impl Error for FreeFormDefinitionError {}
+
+error_messages!(FreeFormElementError {
+    FreeFormElementError::InvalidParameters      => "parameter range is missing, or isn't a number",
+    FreeFormElementError::NotEnoughReferences    => "not enough control points",
+    FreeFormElementError::InvalidReference(_)    => "invalid control point reference",
+    FreeFormElementError::InvalidReferenceFormat => "curves can only reference vertices",
+    FreeFormElementError::MixedReferenceFormats  => "every reference in an element must use the same form",
+    FreeFormElementError::CurveSurfaceMismatch   => "degree doesn't match the element, curves need a curve degree and surfaces a surface degree"
+});
+
+impl Error for FreeFormElementError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            FreeFormElementError::InvalidReference(error) => Some(error),
+            _                                             => None
+        }
+    }
+}
+
+error_messages!(BodyStatementError {
+    BodyStatementError::InvalidParameters  => "parameters are missing, or aren't numbers",
+    BodyStatementError::InvalidAxis        => "\"parm\" must be followed by u or v",
+    BodyStatementError::InvalidReference   => "reference isn't a valid index",
+    BodyStatementError::NotValidForElement => "statement isn't valid for this kind of free-form element",
+    BodyStatementError::Index(_)           => "reference is outside of the buffer it references"
+});
+
+impl Error for BodyStatementError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            BodyStatementError::Index(error) => Some(error),
+            _                                => None
+        }
+    }
+}
+
+error_messages!(ConnectionError {
+    ConnectionError::InvalidParameters => "expected \"surf q0 q1 curv2d\" for both surfaces",
+    ConnectionError::InvalidReference  => "reference isn't a valid index",
+    ConnectionError::Index(_)          => "reference is outside of the elements read so far",
+    ConnectionError::MissingSurface    => "referenced surface doesn't exist",
+    ConnectionError::MissingCurve      => "referenced 2D curve doesn't exist",
+    ConnectionError::InvalidRange      => "parameter range is empty, or outside of the 2D curve's global parameters",
+    ConnectionError::Evaluation(_)     => "seam couldn't be evaluated"
+});
+
+impl Error for ConnectionError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ConnectionError::Index(error)      => Some(error),
+            ConnectionError::Evaluation(error) => Some(error),
+            _                                  => None
+        }
+    }
+}
+
+error_messages!(EvaluationError {
+    EvaluationError::UnsupportedType      => "curve/surface type can't be evaluated",
+    EvaluationError::ControlPointMismatch => "number of control points doesn't match the degree and global parameters",
+    EvaluationError::MissingParameters    => "element with more than one span needs global parameters",
+    EvaluationError::OutOfRange           => "parameter is outside of the global parameters",
+    EvaluationError::InvalidParameters    => "global parameters must increase, and knots can't decrease",
+    EvaluationError::KnotCountMismatch    => "number of knots must be the number of control points plus degree plus one",
+    EvaluationError::InvalidReference(_)  => "control point doesn't exist"
+});
+
+impl Error for EvaluationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            EvaluationError::InvalidReference(error) => Some(error),
+            _                                        => None
+        }
+    }
+}
+
+error_messages!(FreeFormValidationExceptions {
+    FreeFormValidationExceptions::CardinalDegreeNotEqualToThree => "cardinal curves and surfaces must have a degree of 3",
+    FreeFormValidationExceptions::CurveSurfaceMismatch          => "degree and basis matrix disagree on curve or surface",
+    FreeFormValidationExceptions::InvalidMatrixSize             => "basis matrix size doesn't match the degree",
+    FreeFormValidationExceptions::UnknownException              => "unknown validation error"
+});
+impl Error for FreeFormValidationExceptions {}
+
+error_messages!(FreeFormTypesErrors {
+    FreeFormTypesErrors::InvalidFreeFormType => "unknown curve/surface type"
+});
+impl Error for FreeFormTypesErrors {}
+
+error_messages!(UVPairError {
+    UVPairError::InvalidParameters => "parameters must be positive integers",
+    UVPairError::InvalidBufferSize => "expected one value for curves, or two for surfaces"
+});
+impl Error for UVPairError {}
+
+error_messages!(BasisMatrixAttributesError {
+    BasisMatrixAttributesError::InvalidMatrixType    => "invalid basis matrix type",
+    BasisMatrixAttributesError::InvalidBufferSize    => "expected one value for curves, or two for surfaces",
+    BasisMatrixAttributesError::CurveSurfaceMismatch => "step and basis matrix disagree on curve or surface",
+    BasisMatrixAttributesError::MatrixSizeMismatch   => "basis matrix size doesn't match the degree",
+    BasisMatrixAttributesError::UnknownError         => "unknown error"
+});
+impl Error for BasisMatrixAttributesError {}
+
+error_messages!(MatrixError {
+    MatrixError::InvalidDegree        => "basis matrix size doesn't match the degree",
+    MatrixError::CurveSurfaceMismatch => "degree and basis matrix disagree on curve or surface"
+});
+impl Error for MatrixError {}
+
+error_messages!(GroupingError {
+    GroupingError::InvalidParameters  => "wrong number of parameters",
+    GroupingError::InvalidGroupNumber => "group number must be a positive integer, or \"off\"",
+    GroupingError::InvalidResolution  => "merging group needs a resolution greater than 0",
+    GroupingError::InvalidKeyword     => "keyword isn't a grouping statement"
+});
+impl Error for GroupingError {}
+
+error_messages!(RenderStateError {
+    RenderStateError::InvalidParameters    => "wrong number of parameters, or a parameter isn't a number",
+    RenderStateError::InvalidSwitch        => "expected \"on\" or \"off\"",
+    RenderStateError::InvalidLevelOfDetail => "level of detail must be between 0 and 100",
+    RenderStateError::UnknownTechnique     => "unknown approximation technique",
+    RenderStateError::InvalidKeyword       => "keyword isn't a display/render attribute"
+});
+impl Error for RenderStateError {}
+
+error_messages!(UtilityError {
+    UtilityError::ConversionError => "parameter couldn't be converted to a number"
+});
+impl Error for UtilityError {}
+
+error_messages!(MaterialError {
+    MaterialError::UnknownKeyword    => "keyword isn't part of the MTL specification",
+    MaterialError::MissingMaterial   => "statement appears before \"newmtl\"",
+    MaterialError::InvalidParameters => "invalid parameters",
+    MaterialError::InvalidOption     => "invalid texture map option",
+    MaterialError::MissingFileName   => "texture map is missing a file name",
+    MaterialError::IO(_)             => "file or line couldn't be read"
+});
+impl Error for MaterialError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            MaterialError::IO(error) => Some(error.get_ref()),
+            _                        => None
+        }
+    }
+}
@@ -72,9 +72,7 @@ impl BasisMatrixAttributes {
             _                                      => {}
         };
 
-        if self.step.is_curve() && self.matrix.is_curve() {
-            Ok(())
-        } else if self.step.is_surface() && self.matrix.is_surface() {
+        if (self.step.is_curve() && self.matrix.is_curve()) || (self.step.is_surface() && self.matrix.is_surface()) {
             Ok(())
         } else {
             Err(BasisMatrixAttributesError::CurveSurfaceMismatch)
@@ -44,21 +44,15 @@ impl Matrix {
     }
 
     pub fn is_curve(&self) -> bool {
-        match self {
-            Matrix::Curve(_) => true,
-            _                => false
-        }
+        matches!(self, Matrix::Curve(_))
     }
 
     pub fn is_surface(&self) -> bool {
-        match self {
-            Matrix::Surface(_, _) => true,
-            _                     => false
-        }
+        matches!(self, Matrix::Surface(_, _))
     }
 
-    pub fn get_row(&self, row: usize, degree: &Degree) -> Option<Vec<MatrixRow>> {
-        if let Err(_) = self.validate_matrix(degree) {
+    pub fn get_row(&self, row: usize, degree: &Degree) -> Option<Vec<MatrixRow<'_>>> {
+        if self.validate_matrix(degree).is_err() {
             return None;
         }
         
@@ -77,8 +71,8 @@ impl Matrix {
         }
     }
 
-    pub fn get_column(&self, column: usize, degree: &Degree) -> Option<Vec<MatrixColumn>> {
-        if let Err(_) = self.validate_matrix(degree) {
+    pub fn get_column(&self, column: usize, degree: &Degree) -> Option<Vec<MatrixColumn<'_>>> {
+        if self.validate_matrix(degree).is_err() {
             return None;
         }
         
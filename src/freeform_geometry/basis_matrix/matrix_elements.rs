@@ -36,8 +36,7 @@ impl MatrixElements {
         let matrix_size = (degree + 1).pow(2);
 
         if self.len() == matrix_size {
-            let mut ret = Vec::<&'a f32>::new();
-            ret.reserve(degree + 1);
+            let mut ret = Vec::<&'a f32>::with_capacity(degree + 1);
 
             for i in ((column - 1)..matrix_size).step_by(degree + 1) {
                 ret.push(&self.0[i])
@@ -56,8 +55,7 @@ impl MatrixElements {
         let matrix_size = (degree + 1).pow(2);
 
         if self.len() == matrix_size {
-            let mut ret = Vec::<&'a f32>::new();
-            ret.reserve(degree + 1);
+            let mut ret = Vec::<&'a f32>::with_capacity(degree + 1);
 
             let starting_index = (row - 1) * (degree + 1);
 
@@ -73,4 +71,8 @@ impl MatrixElements {
     pub fn len(&self) -> usize {
         self.0.len()
     }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
 }
\ No newline at end of file
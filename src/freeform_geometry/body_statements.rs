@@ -0,0 +1,138 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Freeform Body Statements
+ *      Only valid between a free-form element and its "end" statement
+ *          - Parameter Values      "parm u p1 p2 ..."  | "parm v p1 p2 ..."
+ *              * Global parameters, or knot vector for b-splines
+ *              * v is only valid for surfaces
+ *          - Outer Trimming Loop   "trim u0 u1 curv2d u0 u1 curv2d ..."
+ *          - Inner Trimming Loop   "hole u0 u1 curv2d u0 u1 curv2d ..."
+ *          - Special Curve         "scrv u0 u1 curv2d u0 u1 curv2d ..."
+ *              * Loops and special curves are only valid for surfaces
+ *              * curv2d references a previously defined 2D curve
+ *          - Special Point         "sp vp1 vp2 ..."
+ *              * References parameter space vertices
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::collections::VecDeque;
+
+use crate::keywords;
+use crate::index::{Index, IndexError};
+use crate::utility;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BodyStatementError {
+    InvalidParameters,          // Parameters are missing, or aren't numbers
+    InvalidAxis,                // parm must be followed by u or v
+    InvalidReference,           // Reference isn't a valid index
+    NotValidForElement,         // Statement isn't allowed in this kind of element (ex: trim in a curve)
+    Index(IndexError)           // Reference points outside of the buffer
+}
+
+// A segment of a 2D curve. (u0, u1, curv2d)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CurveReference {
+    pub start: f32,
+    pub end:   f32,
+    pub curve: Index            // 2D curve the segment lies on
+}
+
+// Counts used to resolve relative references in body statements
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct BodyContext {
+    pub is_surface:               bool,
+    pub curve_2d_count:           usize,
+    pub parameter_space_vertices: usize
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FreeFormBody {
+    pub parameters_u:   Vec<f32>,
+    pub parameters_v:   Vec<f32>,
+    pub trim_loops:     Vec<Vec<CurveReference>>,     // One loop per trim statement
+    pub holes:          Vec<Vec<CurveReference>>,     // One loop per hole statement
+    pub special_curves: Vec<Vec<CurveReference>>,     // One curve per scrv statement
+    pub special_points: Vec<Index>                    // Parameter space vertices
+}
+
+impl FreeFormBody {
+    pub fn new() -> FreeFormBody {
+        FreeFormBody::default()
+    }
+
+    // Apply a single body statement. References are resolved to absolute indices immediately.
+    pub fn apply(&mut self, keyword: &str, parameters: &VecDeque<String>, context: &BodyContext) -> Result<(), BodyStatementError> {
+        match keyword {
+            keywords::PARAMETER_VALUE => {
+                let mut parameters = parameters.clone();
+                let axis = parameters.pop_front().ok_or(BodyStatementError::InvalidParameters)?;
+                let values = utility::convert_vec::<f32>(&parameters).ok().ok_or(BodyStatementError::InvalidParameters)?;
+                if values.is_empty() {
+                    return Err(BodyStatementError::InvalidParameters);
+                }
+
+                match axis.as_str() {
+                    keywords::BASIS_MATRIX_U                       => self.parameters_u.extend(values),
+                    keywords::BASIS_MATRIX_V if context.is_surface => self.parameters_v.extend(values),
+                    keywords::BASIS_MATRIX_V                       => return Err(BodyStatementError::NotValidForElement),
+                    _                                              => return Err(BodyStatementError::InvalidAxis)
+                }
+            }
+            keywords::OUTER_TRIM_LOOP |
+            keywords::INNER_TRIM_LOOP |
+            keywords::SPECIAL_CURVE   => {
+                if !context.is_surface {
+                    return Err(BodyStatementError::NotValidForElement);
+                }
+
+                let references = FreeFormBody::parse_curve_references(parameters, context)?;
+                match keyword {
+                    keywords::OUTER_TRIM_LOOP => self.trim_loops.push(references),
+                    keywords::INNER_TRIM_LOOP => self.holes.push(references),
+                    _                         => self.special_curves.push(references)
+                }
+            }
+            keywords::SPECIAL_POINT => {
+                if parameters.is_empty() {
+                    return Err(BodyStatementError::InvalidParameters);
+                }
+                for parameter in parameters {
+                    let index = FreeFormBody::parse_index(parameter, context.parameter_space_vertices)?;
+                    self.special_points.push(index);
+                }
+            }
+            _ => return Err(BodyStatementError::NotValidForElement)
+        }
+        Ok(())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        *self == FreeFormBody::default()
+    }
+
+    // (u0, u1, curv2d) triples
+    fn parse_curve_references(parameters: &VecDeque<String>, context: &BodyContext) -> Result<Vec<CurveReference>, BodyStatementError> {
+        if parameters.is_empty() || parameters.len() % 3 != 0 {
+            return Err(BodyStatementError::InvalidParameters);
+        }
+
+        let mut references = Vec::<CurveReference>::with_capacity(parameters.len() / 3);
+        for i in (0..parameters.len()).step_by(3) {
+            let start = parameters[i].parse::<f32>().ok().ok_or(BodyStatementError::InvalidParameters)?;
+            let end   = parameters[i + 1].parse::<f32>().ok().ok_or(BodyStatementError::InvalidParameters)?;
+            let curve = FreeFormBody::parse_index(&parameters[i + 2], context.curve_2d_count)?;
+            references.push(CurveReference { start, end, curve });
+        }
+        Ok(references)
+    }
+
+    fn parse_index(parameter: &str, length: usize) -> Result<Index, BodyStatementError> {
+        let value = parameter.parse::<isize>().ok().ok_or(BodyStatementError::InvalidReference)?;
+        let index = Index::new(value).ok().ok_or(BodyStatementError::InvalidReference)?;
+        index.to_absolute(length).map_err(BodyStatementError::Index)
+    }
+}
@@ -0,0 +1,150 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Free-Form Connectivity
+ *      "con surf_1 q0_1 q1_1 curv2d_1 surf_2 q0_2 q1_2 curv2d_2"
+ *          - Joins two surfaces along a 2D curve on each of them
+ *          - surf references a surface, curv2d references a 2D curve
+ *              * Relative references are resolved when the statement is read
+ *          - q0, q1 are the parameter range of the 2D curve that makes up the seam
+ *      Validation
+ *          - Referenced surfaces and 2D curves must exist
+ *          - Parameter ranges must be within the domain the 2D curve is evaluated over
+ *              * The global parameters, or for B-splines the knots [degree] to [control points]
+ *          - Optionally, both sides can be evaluated to find gaps in the seam
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::fmt;
+use std::collections::VecDeque;
+
+use crate::index::{Index, IndexError};
+use crate::model::ObjModel;
+use super::evaluation::{self, EvaluationError};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ConnectionError {
+    InvalidParameters,              // Expected two halves of "surf q0 q1 curv2d"
+    InvalidReference,               // Reference isn't a valid index
+    Index(IndexError),              // Relative reference points outside of the elements read so far
+    MissingSurface,                 // Referenced surface doesn't exist
+    MissingCurve,                   // Referenced 2D curve doesn't exist
+    InvalidRange,                   // Range is empty, or outside of the 2D curve's domain
+    Evaluation(EvaluationError)     // Seam couldn't be evaluated
+}
+
+// One side of the seam
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurfaceCurve {
+    pub surface: Index,
+    pub start:   f32,                // q0
+    pub end:     f32,                // q1
+    pub curve:   Index               // 2D curve on the surface
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Connection {
+    pub first:  SurfaceCurve,
+    pub second: SurfaceCurve
+}
+
+impl SurfaceCurve {
+    fn from(parameters: &[String], surface_count: usize, curve_2d_count: usize) -> Result<SurfaceCurve, ConnectionError> {
+        let start = parameters[1].parse::<f32>().ok().ok_or(ConnectionError::InvalidParameters)?;
+        let end   = parameters[2].parse::<f32>().ok().ok_or(ConnectionError::InvalidParameters)?;
+        Ok(SurfaceCurve {
+            surface: SurfaceCurve::parse_index(&parameters[0], surface_count)?,
+            start,
+            end,
+            curve:   SurfaceCurve::parse_index(&parameters[3], curve_2d_count)?
+        })
+    }
+
+    // Absolute references are checked once the whole file is read, they can point ahead.
+    fn parse_index(parameter: &str, length: usize) -> Result<Index, ConnectionError> {
+        let value = parameter.parse::<isize>().ok().ok_or(ConnectionError::InvalidReference)?;
+        let index = Index::new(value).ok().ok_or(ConnectionError::InvalidReference)?;
+        if index.is_relative() {
+            return index.to_absolute(length).map_err(ConnectionError::Index);
+        }
+        Ok(index)
+    }
+
+    pub fn validate(&self, model: &ObjModel) -> Result<(), ConnectionError> {
+        model.surface(self.surface).ok_or(ConnectionError::MissingSurface)?;
+        let curve = model.curve_2d(self.curve).ok_or(ConnectionError::MissingCurve)?;
+
+        if !self.start.is_finite() || !self.end.is_finite() || self.start == self.end {
+            return Err(ConnectionError::InvalidRange);
+        }
+
+        // Checked against the same domain evaluate uses. Types that can't be evaluated have nothing to check against
+        let (first, last) = match evaluation::curve_2d_domain(curve) {
+            Ok(domain)                            => domain,
+            Err(EvaluationError::UnsupportedType) => return Ok(()),
+            Err(error)                            => return Err(ConnectionError::Evaluation(error))
+        };
+        if !(first..=last).contains(&self.start) || !(first..=last).contains(&self.end) {
+            return Err(ConnectionError::InvalidRange);
+        }
+        Ok(())
+    }
+
+    // Point in model space at a fraction [0, 1] of the way along the range
+    pub fn evaluate(&self, model: &ObjModel, fraction: f32) -> Result<[f32; 3], ConnectionError> {
+        let surface = model.surface(self.surface).ok_or(ConnectionError::MissingSurface)?;
+        let curve = model.curve_2d(self.curve).ok_or(ConnectionError::MissingCurve)?;
+
+        let q = self.start + (self.end - self.start) * fraction;
+        let (u, v) = evaluation::evaluate_curve_2d(curve, &model.vertex_buffer, q).map_err(ConnectionError::Evaluation)?;
+        evaluation::evaluate_surface(surface, &model.vertex_buffer, u, v).map_err(ConnectionError::Evaluation)
+    }
+}
+
+impl Connection {
+    pub fn from(parameters: &VecDeque<String>, surface_count: usize, curve_2d_count: usize) -> Result<Connection, ConnectionError> {
+        if parameters.len() != 8 {
+            return Err(ConnectionError::InvalidParameters);
+        }
+
+        let parameters: Vec<String> = parameters.iter().cloned().collect();
+        Ok(Connection {
+            first:  SurfaceCurve::from(&parameters[..4], surface_count, curve_2d_count)?,
+            second: SurfaceCurve::from(&parameters[4..], surface_count, curve_2d_count)?
+        })
+    }
+
+    pub fn validate(&self, model: &ObjModel) -> Result<(), ConnectionError> {
+        self.first.validate(model)?;
+        self.second.validate(model)
+    }
+
+    // Largest distance in model space between the two sides, sampled evenly along both ranges
+    pub fn largest_gap(&self, model: &ObjModel, samples: usize) -> Result<f32, ConnectionError> {
+        let samples = samples.max(1);
+        let mut gap = 0.0f32;
+        for i in 0..=samples {
+            let fraction = i as f32 / samples as f32;
+            let first = self.first.evaluate(model, fraction)?;
+            let second = self.second.evaluate(model, fraction)?;
+
+            let distance = first.iter().zip(second.iter()).map(|(a, b)| (a - b) * (a - b)).sum::<f32>().sqrt();
+            gap = gap.max(distance);
+        }
+        Ok(gap)
+    }
+}
+
+impl fmt::Display for SurfaceCurve {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {} {} {}", self.surface, self.start, self.end, self.curve)
+    }
+}
+
+impl fmt::Display for Connection {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} {}", self.first, self.second)
+    }
+}
@@ -0,0 +1,94 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Bezier Evaluation
+ *      Piecewise bezier curves and surfaces
+ *          - Each span uses degree + 1 control points
+ *          - Neighbouring spans share their end control point
+ *              * Control points = spans * degree + 1
+ *          - Evaluated with de Casteljau's algorithm
+ *          - Derivatives are the same algorithm run on the differences of the control points
+ *              * Scaled from the span [0, 1] back to the global parameters
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use super::{EvaluationError, HomogeneousPoint, global_parameters, locate, lerp, difference};
+
+// Point and its derivatives with respect to t. [point, first, second, ...]
+pub fn curve_derivatives(points: &[HomogeneousPoint], degree: usize, parameters: &[f32], t: f32, order: usize) -> Result<Vec<HomogeneousPoint>, EvaluationError> {
+    let parameters = global_parameters(parameters)?;
+    if degree == 0 || points.len() != (parameters.len() - 1) * degree + 1 {
+        return Err(EvaluationError::ControlPointMismatch);
+    }
+
+    let (span, local) = locate(parameters, t)?;
+    let first = span * degree;
+    Ok(span_derivatives(&points[first..=first + degree], local, parameters[span + 1] - parameters[span], order))
+}
+
+// Point and its partial derivatives. [k][l] is differentiated k times in u, and l times in v. (k + l <= order)
+pub fn surface_derivatives(points: &[HomogeneousPoint], degree: (usize, usize), parameters: (&[f32], &[f32]), t: (f32, f32), order: usize) -> Result<Vec<Vec<HomogeneousPoint>>, EvaluationError> {
+    let (degree_u, degree_v) = degree;
+    let (parameters_u, parameters_v) = (global_parameters(parameters.0)?, global_parameters(parameters.1)?);
+
+    let width  = (parameters_u.len() - 1) * degree_u + 1;
+    let height = (parameters_v.len() - 1) * degree_v + 1;
+    if degree_u == 0 || degree_v == 0 || points.len() != width * height {
+        return Err(EvaluationError::ControlPointMismatch);
+    }
+
+    let (span_u, local_u) = locate(parameters_u, t.0)?;
+    let (span_v, local_v) = locate(parameters_v, t.1)?;
+    let (first_u, first_v) = (span_u * degree_u, span_v * degree_v);
+    let length_u = parameters_u[span_u + 1] - parameters_u[span_u];
+    let length_v = parameters_v[span_v + 1] - parameters_v[span_v];
+
+    // Differentiate each row of the span in u, then each resulting column in v
+    let mut rows = Vec::<Vec<HomogeneousPoint>>::with_capacity(degree_v + 1);
+    for row in first_v..=first_v + degree_v {
+        let start = row * width + first_u;
+        rows.push(span_derivatives(&points[start..=start + degree_u], local_u, length_u, order));
+    }
+
+    let mut derivatives = Vec::<Vec<HomogeneousPoint>>::with_capacity(order + 1);
+    for k in 0..=order {
+        let column: Vec<HomogeneousPoint> = rows.iter().map(|row| row[k]).collect();
+        derivatives.push(span_derivatives(&column, local_v, length_v, order - k));
+    }
+    Ok(derivatives)
+}
+
+// Derivatives of a single span. Each order is evaluated from the differences of the order before it.
+fn span_derivatives(points: &[HomogeneousPoint], t: f32, length: f32, order: usize) -> Vec<HomogeneousPoint> {
+    let degree = points.len() - 1;
+    let mut differences = points.to_vec();
+    let mut scale = 1.0;
+
+    let mut derivatives = Vec::<HomogeneousPoint>::with_capacity(order + 1);
+    for k in 0..=order {
+        // Derivatives past the degree vanish
+        if differences.is_empty() {
+            derivatives.push([0.0; 4]);
+            continue;
+        }
+
+        let point = de_casteljau(&mut differences.clone(), t);
+        derivatives.push(point.map(|component| component * scale));
+
+        scale *= (degree - k) as f32 / length;
+        differences = differences.windows(2).map(|pair| difference(pair[1], pair[0])).collect();
+    }
+    derivatives
+}
+
+// Repeated linear interpolation. Overwrites the points.
+pub(crate) fn de_casteljau(points: &mut [HomogeneousPoint], t: f32) -> HomogeneousPoint {
+    for level in 1..points.len() {
+        for i in 0..points.len() - level {
+            points[i] = lerp(points[i], points[i + 1], t);
+        }
+    }
+    points[0]
+}
@@ -0,0 +1,104 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * B-Spline Evaluation
+ *      B-spline and NURBS curves and surfaces
+ *          - The "parm" statements are the knot vector
+ *              * Knots = control points + degree + 1
+ *              * Knots can repeat, but never decrease
+ *          - Defined between the knots [degree] and [control points]
+ *          - Evaluated with de Boor's algorithm
+ *          - Derivatives are b-splines of one degree lower, with control points from the differences
+ *              * The end knots are dropped for each order
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use super::{EvaluationError, HomogeneousPoint, lerp, difference};
+
+// Point and its derivatives with respect to t. [point, first, second, ...]
+pub fn curve_derivatives(points: &[HomogeneousPoint], degree: usize, knots: &[f32], t: f32, order: usize) -> Result<Vec<HomogeneousPoint>, EvaluationError> {
+    validate(points.len(), degree, knots)?;
+    if !(knots[degree]..=knots[points.len()]).contains(&t) {
+        return Err(EvaluationError::OutOfRange);
+    }
+
+    let mut control = points.to_vec();
+    let mut derivatives = Vec::<HomogeneousPoint>::with_capacity(order + 1);
+    for k in 0..=order {
+        // Derivatives past the degree vanish
+        if k > degree {
+            derivatives.push([0.0; 4]);
+            continue;
+        }
+
+        let (knots, degree) = (&knots[k..knots.len() - k], degree - k);
+        derivatives.push(de_boor(&control, degree, knots, t));
+
+        // Control points of the next derivative. Repeated knots have no span to differentiate
+        control = control.windows(2).enumerate().map(|(i, pair)| {
+            let length = knots[i + degree + 1] - knots[i + 1];
+            let scale = if length > 0.0 { degree as f32 / length } else { 0.0 };
+            difference(pair[1], pair[0]).map(|component| component * scale)
+        }).collect();
+    }
+    Ok(derivatives)
+}
+
+// Point and its partial derivatives. [k][l] is differentiated k times in u, and l times in v. (k + l <= order)
+pub fn surface_derivatives(points: &[HomogeneousPoint], degree: (usize, usize), knots: (&[f32], &[f32]), t: (f32, f32), order: usize) -> Result<Vec<Vec<HomogeneousPoint>>, EvaluationError> {
+    let (degree_u, degree_v) = degree;
+    let width  = knots.0.len().saturating_sub(degree_u + 1);
+    let height = knots.1.len().saturating_sub(degree_v + 1);
+    if width == 0 || height == 0 || points.len() != width * height {
+        return Err(EvaluationError::KnotCountMismatch);
+    }
+
+    // Differentiate each row in u, then each resulting column in v
+    let mut rows = Vec::<Vec<HomogeneousPoint>>::with_capacity(height);
+    for row in points.chunks(width) {
+        rows.push(curve_derivatives(row, degree_u, knots.0, t.0, order)?);
+    }
+
+    let mut derivatives = Vec::<Vec<HomogeneousPoint>>::with_capacity(order + 1);
+    for k in 0..=order {
+        let column: Vec<HomogeneousPoint> = rows.iter().map(|row| row[k]).collect();
+        derivatives.push(curve_derivatives(&column, degree_v, knots.1, t.1, order - k)?);
+    }
+    Ok(derivatives)
+}
+
+// Knots can't decrease, and the curve can't be empty
+pub(crate) fn validate(count: usize, degree: usize, knots: &[f32]) -> Result<(), EvaluationError> {
+    if count <= degree {
+        return Err(EvaluationError::ControlPointMismatch);
+    }
+    if knots.len() != count + degree + 1 {
+        return Err(EvaluationError::KnotCountMismatch);
+    }
+    if knots.windows(2).any(|pair| pair[1] < pair[0]) || knots[degree] >= knots[count] {
+        return Err(EvaluationError::InvalidParameters);
+    }
+    Ok(())
+}
+
+// Repeated linear interpolation between the control points of the knot span containing t
+pub(crate) fn de_boor(points: &[HomogeneousPoint], degree: usize, knots: &[f32], t: f32) -> HomogeneousPoint {
+    let span = find_span(knots, degree, points.len(), t);
+    let mut points = points[span - degree..=span].to_vec();
+
+    for level in 1..=degree {
+        for j in (level..=degree).rev() {
+            let (start, end) = (knots[span - degree + j], knots[span + 1 + j - level]);
+            let alpha = if end > start { (t - start) / (end - start) } else { 0.0 };
+            points[j] = lerp(points[j - 1], points[j], alpha);
+        }
+    }
+    points[degree]
+}
+
+// Last non-empty knot span starting at or before t. The end of the curve belongs to the last span
+fn find_span(knots: &[f32], degree: usize, count: usize, t: f32) -> usize {
+    (degree..count).rev().find(|span| knots[*span] <= t && knots[*span] < knots[*span + 1]).unwrap_or(degree)
+}
@@ -0,0 +1,71 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Cardinal Evaluation
+ *      Cardinal (Catmull-Rom) curves and surfaces. Always degree 3
+ *          - Each span uses four control points, and runs between the middle two
+ *          - Neighbouring spans share three control points
+ *              * Control points = global parameters + 2
+ *          - Tangents at the ends of a span are half the difference of the points around them
+ *          - Each span is an equivalent cubic bezier span. Evaluation converts, then evaluates that
+ *              * (P0, P1, P2, P3) -> (P1, P1 + (P2 - P0) / 6, P2 - (P3 - P1) / 6, P2)
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use super::{EvaluationError, HomogeneousPoint, global_parameters, bezier};
+
+// Point and its derivatives with respect to t. [point, first, second, ...]
+pub fn curve_derivatives(points: &[HomogeneousPoint], parameters: &[f32], t: f32, order: usize) -> Result<Vec<HomogeneousPoint>, EvaluationError> {
+    if points.len() != global_parameters(parameters)?.len() + 2 {
+        return Err(EvaluationError::ControlPointMismatch);
+    }
+    bezier::curve_derivatives(&to_bezier(points)?, 3, parameters, t, order)
+}
+
+// Point and its partial derivatives. [k][l] is differentiated k times in u, and l times in v. (k + l <= order)
+pub fn surface_derivatives(points: &[HomogeneousPoint], parameters: (&[f32], &[f32]), t: (f32, f32), order: usize) -> Result<Vec<Vec<HomogeneousPoint>>, EvaluationError> {
+    let width  = global_parameters(parameters.0)?.len() + 2;
+    let height = global_parameters(parameters.1)?.len() + 2;
+    if points.len() != width * height {
+        return Err(EvaluationError::ControlPointMismatch);
+    }
+    bezier::surface_derivatives(&surface_to_bezier(points, width)?, (3, 3), parameters, t, order)
+}
+
+// Control points of the equivalent piecewise cubic bezier curve. Neighbouring spans share their end point
+pub fn to_bezier(points: &[HomogeneousPoint]) -> Result<Vec<HomogeneousPoint>, EvaluationError> {
+    if points.len() < 4 {
+        return Err(EvaluationError::ControlPointMismatch);
+    }
+
+    let mut bezier = Vec::<HomogeneousPoint>::with_capacity((points.len() - 3) * 3 + 1);
+    bezier.push(points[1]);
+    for span in points.windows(4) {
+        let [p0, p1, p2, p3] = [span[0], span[1], span[2], span[3]];
+        bezier.push(std::array::from_fn(|i| p1[i] + (p2[i] - p0[i]) / 6.0));
+        bezier.push(std::array::from_fn(|i| p2[i] - (p3[i] - p1[i]) / 6.0));
+        bezier.push(p2);
+    }
+    Ok(bezier)
+}
+
+// Control points of the equivalent piecewise bezier surface. Rows are converted in u, then columns in v
+pub fn surface_to_bezier(points: &[HomogeneousPoint], width: usize) -> Result<Vec<HomogeneousPoint>, EvaluationError> {
+    if width == 0 || points.is_empty() || points.len() % width != 0 {
+        return Err(EvaluationError::ControlPointMismatch);
+    }
+
+    let mut rows = Vec::<Vec<HomogeneousPoint>>::with_capacity(points.len() / width);
+    for row in points.chunks(width) {
+        rows.push(to_bezier(row)?);
+    }
+
+    // Convert each column, then put the points back in rows
+    let mut columns = Vec::<Vec<HomogeneousPoint>>::with_capacity(rows[0].len());
+    for column in 0..rows[0].len() {
+        columns.push(to_bezier(&rows.iter().map(|row| row[column]).collect::<Vec<HomogeneousPoint>>())?);
+    }
+    Ok((0..columns[0].len()).flat_map(|row| columns.iter().map(move |column| column[row])).collect())
+}
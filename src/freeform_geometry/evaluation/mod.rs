@@ -0,0 +1,257 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Free-Form Evaluation
+ *      Evaluates free-form elements at a parameter value
+ *          - Control points are weighted when the definition is rational
+ *          - Global parameters come from the "parm" body statements
+ *              * Without them, a single span over [0, 1] is assumed
+ *              * B-splines use them as the knot vector, and always need them
+ *          - Surface control points are listed u first, then v
+ *          - Derivatives are with respect to the global parameters
+ *              * [point, first, second, ...] for curves
+ *              * [k][l] for surfaces. Differentiated k times in u, and l times in v
+ *      Supported Types
+ *          - Bezier
+ *          - B-spline (NURBS when rational)
+ *          - Cardinal
+ *              * Can also be converted to bezier control points
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+pub mod bezier;
+pub mod bspline;
+pub mod cardinal;
+
+use crate::vertex_buffer::{VertexBuffer, VertexBufferError};
+use crate::vertex_data::Vertex;
+use super::freeform_definition::FreeFormDefinition;
+use super::freeform_elements::{Curve, Curve2D, Surface};
+use super::freeform_types::FreeFormType;
+use super::uv_pair::UVPair;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum EvaluationError {
+    UnsupportedType,                    // Element type can't be evaluated
+    ControlPointMismatch,               // Number of control points doesn't fit the degree and global parameters
+    MissingParameters,                  // Elements with more than one span need global parameters
+    OutOfRange,                         // Parameter is outside of the global parameters
+    InvalidParameters,                  // Global parameters don't increase, or knots decrease
+    KnotCountMismatch,                  // Knots must be control points + degree + 1
+    InvalidReference(VertexBufferError) // Control point doesn't exist
+}
+
+// Control point with its coordinates premultiplied by the weight. (x*w, y*w, z*w, w)
+pub type HomogeneousPoint = [f32; 4];
+
+// Point on a curve in model space
+pub fn evaluate_curve(curve: &Curve, buffer: &VertexBuffer, t: f32) -> Result<[f32; 3], EvaluationError> {
+    Ok(evaluate_curve_derivatives(curve, buffer, t, 0)?[0])
+}
+
+// Point on a curve in model space, and its derivatives with respect to t. [point, first, second, ...]
+pub fn evaluate_curve_derivatives(curve: &Curve, buffer: &VertexBuffer, t: f32, order: usize) -> Result<Vec<[f32; 3]>, EvaluationError> {
+    let mut points = Vec::<HomogeneousPoint>::with_capacity(curve.vertices.len());
+    for index in &curve.vertices {
+        let vertex = buffer.get_vertex(*index).map_err(EvaluationError::InvalidReference)?;
+        points.push(control_point(&curve.definition, [vertex.x, vertex.y, vertex.z], vertex.w));
+    }
+
+    let derivatives = curve_derivatives(&curve.definition, &points, &curve.body.parameters_u, t, order)?;
+    Ok(project_curve_derivatives(&derivatives))
+}
+
+// Point on a 2D curve in parameter space. (u, v)
+pub fn evaluate_curve_2d(curve: &Curve2D, buffer: &VertexBuffer, t: f32) -> Result<(f32, f32), EvaluationError> {
+    Ok(evaluate_curve_2d_derivatives(curve, buffer, t, 0)?[0])
+}
+
+// Point on a 2D curve in parameter space, and its derivatives with respect to t
+pub fn evaluate_curve_2d_derivatives(curve: &Curve2D, buffer: &VertexBuffer, t: f32, order: usize) -> Result<Vec<(f32, f32)>, EvaluationError> {
+    let mut points = Vec::<HomogeneousPoint>::with_capacity(curve.vertices.len());
+    for index in &curve.vertices {
+        let vertex = buffer.get_paramter_space_vertex(*index).map_err(EvaluationError::InvalidReference)?;
+        points.push(control_point(&curve.definition, [vertex.u, vertex.v, 0.0], vertex.w));
+    }
+
+    let derivatives = curve_derivatives(&curve.definition, &points, &curve.body.parameters_u, t, order)?;
+    Ok(project_curve_derivatives(&derivatives).into_iter().map(|[u, v, _]| (u, v)).collect())
+}
+
+// Point on a surface in model space
+pub fn evaluate_surface(surface: &Surface, buffer: &VertexBuffer, u: f32, v: f32) -> Result<[f32; 3], EvaluationError> {
+    Ok(evaluate_surface_derivatives(surface, buffer, u, v, 0)?[0][0])
+}
+
+// Point on a surface in model space, and its partial derivatives. [k][l] is differentiated k times in u, and l times in v
+pub fn evaluate_surface_derivatives(surface: &Surface, buffer: &VertexBuffer, u: f32, v: f32, order: usize) -> Result<Vec<Vec<[f32; 3]>>, EvaluationError> {
+    let mut points = Vec::<HomogeneousPoint>::with_capacity(surface.references.len());
+    for reference in &surface.references {
+        let vertex = buffer.get_vertex(reference.vertex).map_err(EvaluationError::InvalidReference)?;
+        points.push(control_point(&surface.definition, [vertex.x, vertex.y, vertex.z], vertex.w));
+    }
+
+    let parameters = (surface.body.parameters_u.as_slice(), surface.body.parameters_v.as_slice());
+    let derivatives = surface_derivatives(&surface.definition, &points, parameters, (u, v), order)?;
+    Ok(project_surface_derivatives(&derivatives))
+}
+
+// Control points of the cubic bezier curve equal to a cardinal curve. It uses the same global parameters
+pub fn cardinal_to_bezier(curve: &Curve, buffer: &VertexBuffer) -> Result<Vec<Vertex>, EvaluationError> {
+    if curve.definition.form_type != FreeFormType::Cardinal {
+        return Err(EvaluationError::UnsupportedType);
+    }
+
+    let mut points = Vec::<HomogeneousPoint>::with_capacity(curve.vertices.len());
+    for index in &curve.vertices {
+        let vertex = buffer.get_vertex(*index).map_err(EvaluationError::InvalidReference)?;
+        points.push(control_point(&curve.definition, [vertex.x, vertex.y, vertex.z], vertex.w));
+    }
+
+    let bezier = cardinal::to_bezier(&points)?;
+    Ok(bezier.iter().map(|point| {
+        let weight = divisor(*point);
+        Vertex { x: point[0] / weight, y: point[1] / weight, z: point[2] / weight, w: point[3] }
+    }).collect())
+}
+
+// Parameter range a 2D curve is evaluated over
+pub fn curve_2d_domain(curve: &Curve2D) -> Result<(f32, f32), EvaluationError> {
+    curve_domain(&curve.definition, &curve.body.parameters_u, curve.vertices.len())
+}
+
+// Parameter range a curve described by a definition is evaluated over.
+// B-splines run from knot [degree] to knot [control points], everything else over its global parameters
+pub fn curve_domain(definition: &FreeFormDefinition, parameters: &[f32], count: usize) -> Result<(f32, f32), EvaluationError> {
+    let degree = match definition.degree {
+        UVPair::Curve(u) | UVPair::Surface(u, _) => u
+    };
+
+    match &definition.form_type {
+        FreeFormType::BSpline                        => {
+            bspline::validate(count, degree, parameters)?;
+            Ok((parameters[degree], parameters[count]))
+        }
+        FreeFormType::Bezier | FreeFormType::Cardinal => {
+            let parameters = global_parameters(parameters)?;
+            Ok((parameters[0], parameters[parameters.len() - 1]))
+        }
+        _                                            => Err(EvaluationError::UnsupportedType)
+    }
+}
+
+// Derivatives of the weighted curve described by a definition. (x*w, y*w, z*w, w)
+pub fn curve_derivatives(definition: &FreeFormDefinition, points: &[HomogeneousPoint], parameters: &[f32], t: f32, order: usize) -> Result<Vec<HomogeneousPoint>, EvaluationError> {
+    let degree = match definition.degree {
+        UVPair::Curve(u) | UVPair::Surface(u, _) => u
+    };
+
+    match &definition.form_type {
+        FreeFormType::Bezier   => bezier::curve_derivatives(points, degree, parameters, t, order),
+        FreeFormType::BSpline  => bspline::curve_derivatives(points, degree, parameters, t, order),
+        FreeFormType::Cardinal => cardinal::curve_derivatives(points, parameters, t, order),
+        _                      => Err(EvaluationError::UnsupportedType)
+    }
+}
+
+// Derivatives of the weighted surface described by a definition. [k][l] is differentiated k times in u, and l times in v
+pub fn surface_derivatives(definition: &FreeFormDefinition, points: &[HomogeneousPoint], parameters: (&[f32], &[f32]), t: (f32, f32), order: usize) -> Result<Vec<Vec<HomogeneousPoint>>, EvaluationError> {
+    let degree = match definition.degree {
+        UVPair::Surface(u, v) => (u, v),
+        UVPair::Curve(u)      => (u, u)
+    };
+
+    match &definition.form_type {
+        FreeFormType::Bezier   => bezier::surface_derivatives(points, degree, parameters, t, order),
+        FreeFormType::BSpline  => bspline::surface_derivatives(points, degree, parameters, t, order),
+        FreeFormType::Cardinal => cardinal::surface_derivatives(points, parameters, t, order),
+        _                      => Err(EvaluationError::UnsupportedType)
+    }
+}
+
+// Weights only apply to rational elements
+pub fn control_point(definition: &FreeFormDefinition, point: [f32; 3], weight: f32) -> HomogeneousPoint {
+    let weight = if definition.rational { weight } else { 1.0 };
+    [point[0] * weight, point[1] * weight, point[2] * weight, weight]
+}
+
+// Divide the weight back out of curve derivatives. Each order uses the quotient rule on the orders before it.
+pub fn project_curve_derivatives(derivatives: &[HomogeneousPoint]) -> Vec<[f32; 3]> {
+    let weight = divisor(derivatives[0]);
+
+    let mut projected = Vec::<[f32; 3]>::with_capacity(derivatives.len());
+    for (k, derivative) in derivatives.iter().enumerate() {
+        let mut point = [derivative[0], derivative[1], derivative[2]];
+        for i in 1..=k {
+            point = subtract_scaled(point, projected[k - i], binomial(k, i) * derivatives[i][3]);
+        }
+        projected.push(point.map(|component| component / weight));
+    }
+    projected
+}
+
+// Divide the weight back out of surface derivatives
+pub fn project_surface_derivatives(derivatives: &[Vec<HomogeneousPoint>]) -> Vec<Vec<[f32; 3]>> {
+    let weight = divisor(derivatives[0][0]);
+
+    let mut projected = Vec::<Vec<[f32; 3]>>::with_capacity(derivatives.len());
+    for (k, row) in derivatives.iter().enumerate() {
+        projected.push(Vec::with_capacity(row.len()));
+        for (l, derivative) in row.iter().enumerate() {
+            let mut point = [derivative[0], derivative[1], derivative[2]];
+            for i in 0..=k {
+                for j in (0..=l).filter(|j| i + j > 0) {
+                    point = subtract_scaled(point, projected[k - i][l - j], binomial(k, i) * binomial(l, j) * derivatives[i][j][3]);
+                }
+            }
+            projected[k].push(point.map(|component| component / weight));
+        }
+    }
+    projected
+}
+
+// Global parameters of an element. Elements without them are a single span over [0, 1]
+pub(crate) fn global_parameters(parameters: &[f32]) -> Result<&[f32], EvaluationError> {
+    match parameters.len() {
+        0 => Ok(&[0.0, 1.0]),
+        1 => Err(EvaluationError::MissingParameters),
+        _ if parameters.windows(2).any(|pair| pair[1] <= pair[0]) => Err(EvaluationError::InvalidParameters),
+        _ => Ok(parameters)
+    }
+}
+
+// Span containing t, and t relative to that span [0, 1]
+pub(crate) fn locate(parameters: &[f32], t: f32) -> Result<(usize, f32), EvaluationError> {
+    let (first, last) = (parameters[0], parameters[parameters.len() - 1]);
+    if !(first..=last).contains(&t) {
+        return Err(EvaluationError::OutOfRange);
+    }
+
+    let span = parameters[1..parameters.len() - 1].iter().take_while(|parameter| **parameter <= t).count();
+    let (start, end) = (parameters[span], parameters[span + 1]);
+    let local = if end > start { (t - start) / (end - start) } else { 0.0 };
+    Ok((span, local))
+}
+
+pub(crate) fn lerp(a: HomogeneousPoint, b: HomogeneousPoint, t: f32) -> HomogeneousPoint {
+    [a[0] + (b[0] - a[0]) * t, a[1] + (b[1] - a[1]) * t, a[2] + (b[2] - a[2]) * t, a[3] + (b[3] - a[3]) * t]
+}
+
+pub(crate) fn difference(a: HomogeneousPoint, b: HomogeneousPoint) -> HomogeneousPoint {
+    [a[0] - b[0], a[1] - b[1], a[2] - b[2], a[3] - b[3]]
+}
+
+fn subtract_scaled(a: [f32; 3], b: [f32; 3], scale: f32) -> [f32; 3] {
+    [a[0] - b[0] * scale, a[1] - b[1] * scale, a[2] - b[2] * scale]
+}
+
+fn binomial(n: usize, k: usize) -> f32 {
+    (0..k).fold(1.0, |value, i| value * (n - i) as f32 / (i + 1) as f32)
+}
+
+// Points at infinity (w = 0) are left unprojected
+fn divisor(point: HomogeneousPoint) -> f32 {
+    if point[3] == 0.0 { 1.0 } else { point[3] }
+}
@@ -8,6 +8,7 @@
  * 
  * ------------------------------------------------------------------------------------*/
 use std::str::FromStr;
+use std::io::BufRead;
 use std::collections::VecDeque;
 
 use super::freeform_types::FreeFormType;
@@ -16,6 +17,7 @@ use super::uv_pair::UVPairError;
 
 use crate::freeform_geometry::basis_matrix::basis_matrix_attributes::{BasisMatrixAttributes, BasisMatrixAttributesError};
 use crate::parser::ObjParser;
+use crate::span::{Span, Spanned, WithSpan};
 use crate::keywords;
 use crate::utility;
 
@@ -32,7 +34,7 @@ pub enum FreeFormDefinitionError {
     UnknownError
 }
 
-#[derive(Debug, PartialEq)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum FreeFormValidationExceptions {
     CardinalDegreeNotEqualToThree,
     CurveSurfaceMismatch,
@@ -40,6 +42,44 @@ pub enum FreeFormValidationExceptions {
     UnknownException
 }
 
+// Free-form attributes are state-setting, and can be supplied one statement at a time.
+// This holds the attributes read so far until a complete definition can be produced.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct FreeFormState {
+    pub form_type: Option<FreeFormType>,    // Required
+    pub rational:  Option<bool>,            // Not Required
+    pub degree:    Option<Degree>,          // Required
+}
+
+impl FreeFormState {
+    // Apply a single attribute statement. Returns false if the keyword isn't a free-form attribute.
+    pub fn apply(&mut self, keyword: &str, parameters: &mut VecDeque<String>) -> Result<bool, FreeFormDefinitionError> {
+        match keyword {
+            keywords::CURVE_SURFACE_TYPE => (self.form_type, self.rational) = FreeFormDefinition::parse_curve_surface_type(parameters)?,
+            keywords::DEGREE             => self.degree = FreeFormDefinition::parse_degree(parameters)?,
+            keywords::BASIS_MATRIX       => {
+                let form_type = self.form_type.as_ref().ok_or(FreeFormDefinitionError::InvalidFormType)?;
+                self.form_type = Some(FreeFormDefinition::parse_matrix(form_type, parameters)?);
+            }
+            keywords::STEP_SIZE          => {
+                let form_type = self.form_type.as_ref().ok_or(FreeFormDefinitionError::InvalidFormType)?;
+                self.form_type = Some(FreeFormDefinition::parse_matrix_step(form_type, parameters)?);
+            }
+            _                            => return Ok(false)
+        }
+        Ok(true)
+    }
+
+    // Get the definition described by the current state. Type and degree must have been supplied.
+    pub fn definition(&self) -> Result<FreeFormDefinition, FreeFormDefinitionError> {
+        if let (Some(form_type), Some(degree)) = (&self.form_type, self.degree) {
+            Ok(FreeFormDefinition { form_type: form_type.clone(), rational: self.rational.unwrap_or(false), degree })
+        } else {
+            Err(FreeFormDefinitionError::MalformedDefinition)
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Default)]
 pub struct FreeFormDefinition {
     pub form_type:  FreeFormType,
@@ -52,43 +92,23 @@ impl FreeFormDefinition {
         FreeFormDefinition{form_type, rational, degree}
     }
 
-    #[allow(unused_assignments)]
-    pub fn parse(parser: &mut ObjParser) -> Result<FreeFormDefinition, FreeFormDefinitionError> {
-        let mut free_form_type: Option<FreeFormType>      = None;    // Required
-        let mut degree: Option<Degree>                    = None;    // Required
-        let mut rational: Option<bool>                    = None;    // Not Required
+    // Errors are reported with the span of the offending statement.
+    // Missing attributes are reported at the span of the last attribute read.
+    pub fn parse<R: BufRead>(parser: &mut ObjParser<R>) -> Result<FreeFormDefinition, Spanned<FreeFormDefinitionError>> {
+        let mut state = FreeFormState::default();
+        let mut last_span = Span::default();
 
         while let Some(mut line) = parser.peek_line() {
-            if let Some(keyword) = line.keyword {
-                match keyword.as_str() {
-                    keywords::CURVE_SURFACE_TYPE => (free_form_type, rational) = FreeFormDefinition::parse_curve_surface_type(&line.parameters)?,
-                    keywords::DEGREE             => degree = FreeFormDefinition::parse_degree(&line.parameters)?,
-                    keywords::BASIS_MATRIX       => {
-                        if let Some(form_type) = free_form_type {
-                            free_form_type = Some(FreeFormDefinition::parse_matrix(&form_type, &mut line.parameters)?);
-                        } else {
-                            return Err(FreeFormDefinitionError::InvalidFormType);
-                        }
-                    }
-                    keywords::STEP_SIZE          => {
-                        if let Some(form_type) = free_form_type {
-                            free_form_type = Some(FreeFormDefinition::parse_matrix_step(&form_type, &mut line.parameters)?);
-                        } else {
-                            return Err(FreeFormDefinitionError::InvalidFormType);
-                        }
-                    }
-                    _                            => break
+            if let Some(keyword) = line.keyword.take() {
+                if !state.apply(keyword.as_str(), &mut line.parameters).with_span(line.span)? {
+                    break;
                 }
+                last_span = line.span;
             }
             parser.skip_line();
         }
 
-        if let (Some(form_type), Some(degree)) = (free_form_type, degree) {
-            Ok(FreeFormDefinition { form_type, rational: rational.unwrap_or(false), degree })
-        } else {
-            Err(FreeFormDefinitionError::MalformedDefinition)
-        }
-
+        state.definition().with_span(last_span)
     }
 
     pub fn validate(&self) -> Result<(), FreeFormValidationExceptions> {
@@ -101,10 +121,10 @@ impl FreeFormDefinition {
 
         if let FreeFormType::BasisMatrix(atributes) = &self.form_type {
             match atributes.validate(&self.degree) {
-                Ok(())                                                => return Ok(()),
-                Err(BasisMatrixAttributesError::MatrixSizeMismatch)   => return Err(FreeFormValidationExceptions::InvalidMatrixSize),
-                Err(BasisMatrixAttributesError::CurveSurfaceMismatch) => return Err(FreeFormValidationExceptions::CurveSurfaceMismatch),
-                _                                                     => return Err(FreeFormValidationExceptions::UnknownException)
+                Ok(())                                                => Ok(()),
+                Err(BasisMatrixAttributesError::MatrixSizeMismatch)   => Err(FreeFormValidationExceptions::InvalidMatrixSize),
+                Err(BasisMatrixAttributesError::CurveSurfaceMismatch) => Err(FreeFormValidationExceptions::CurveSurfaceMismatch),
+                _                                                     => Err(FreeFormValidationExceptions::UnknownException)
             }
         } else {
             Ok(())
@@ -0,0 +1,160 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Freeform Elements
+ *      Elements that use the active free-form definition
+ *          - Curve     "curv u0 u1 v1 v2 ..."
+ *              * Parameter range, and at least two control vertices
+ *          - 2D Curve  "curv2 vp1 vp2 ..."
+ *              * At least two parameter space vertices
+ *          - Surface   "surf s0 s1 t0 t1 v1/vt1/vn1 v2/vt2/vn2 ..."
+ *              * Parameter ranges, and control vertices
+ *              * Texture coordinates and normals are optional, but every reference must use the same form
+ *      Each element keeps the definition that was active when it was read.
+ *      Curves require a curve degree, and surfaces require a surface degree.
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::rc::Rc;
+use std::collections::VecDeque;
+
+use crate::index::{Index, IndexError};
+use crate::vertex_buffer::VertexCounts;
+use crate::polygonal_geometry::index_triplet::{IndexTriplet, IndexTripletError, TripletFormat};
+use super::freeform_definition::FreeFormDefinition;
+use super::body_statements::FreeFormBody;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FreeFormElementError {
+    InvalidParameters,                  // Parameter range is missing, or isn't a number
+    NotEnoughReferences,                // Curves need at least two control points
+    InvalidReference(IndexTripletError),
+    InvalidReferenceFormat,             // Curves only reference vertices
+    MixedReferenceFormats,              // All surface references must use the same form
+    CurveSurfaceMismatch                // Degree doesn't match the element (ex: curve with a surface degree)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve {
+    pub start:      f32,                        // u0
+    pub end:        f32,                        // u1
+    pub vertices:   Vec<Index>,                 // Control points
+    pub definition: Rc<FreeFormDefinition>,
+    pub body:       FreeFormBody                // Body statements up to the "end" statement
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Curve2D {
+    pub vertices:   Vec<Index>,                 // Parameter space control points
+    pub definition: Rc<FreeFormDefinition>,
+    pub body:       FreeFormBody
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Surface {
+    pub s_range:    (f32, f32),                 // s0, s1
+    pub t_range:    (f32, f32),                 // t0, t1
+    pub references: Vec<IndexTriplet>,          // Control points
+    pub definition: Rc<FreeFormDefinition>,
+    pub body:       FreeFormBody
+}
+
+impl Curve {
+    pub fn from(parameters: &VecDeque<String>, definition: Rc<FreeFormDefinition>) -> Result<Curve, FreeFormElementError> {
+        if !definition.degree.is_curve() {
+            return Err(FreeFormElementError::CurveSurfaceMismatch);
+        }
+
+        let mut parameters = parameters.clone();
+        let start = parse_value(&mut parameters)?;
+        let end = parse_value(&mut parameters)?;
+        let vertices = parse_vertices(&parameters)?;
+
+        Ok(Curve { start, end, vertices, definition, body: FreeFormBody::new() })
+    }
+
+    // Convert every reference to an absolute index. Must be done before more vertex data is read.
+    pub fn resolve(&mut self, counts: &VertexCounts) -> Result<(), IndexError> {
+        for vertex in self.vertices.iter_mut() {
+            *vertex = vertex.to_absolute(counts.vertices)?;
+        }
+        Ok(())
+    }
+}
+
+impl Curve2D {
+    pub fn from(parameters: &VecDeque<String>, definition: Rc<FreeFormDefinition>) -> Result<Curve2D, FreeFormElementError> {
+        if !definition.degree.is_curve() {
+            return Err(FreeFormElementError::CurveSurfaceMismatch);
+        }
+
+        let vertices = parse_vertices(parameters)?;
+        Ok(Curve2D { vertices, definition, body: FreeFormBody::new() })
+    }
+
+    // 2D curves reference parameter space vertices
+    pub fn resolve(&mut self, counts: &VertexCounts) -> Result<(), IndexError> {
+        for vertex in self.vertices.iter_mut() {
+            *vertex = vertex.to_absolute(counts.parameter_space_vertices)?;
+        }
+        Ok(())
+    }
+}
+
+impl Surface {
+    pub fn from(parameters: &VecDeque<String>, definition: Rc<FreeFormDefinition>) -> Result<Surface, FreeFormElementError> {
+        if !definition.degree.is_surface() {
+            return Err(FreeFormElementError::CurveSurfaceMismatch);
+        }
+
+        let mut parameters = parameters.clone();
+        let s_range = (parse_value(&mut parameters)?, parse_value(&mut parameters)?);
+        let t_range = (parse_value(&mut parameters)?, parse_value(&mut parameters)?);
+
+        if parameters.is_empty() {
+            return Err(FreeFormElementError::NotEnoughReferences);
+        }
+
+        let mut references = Vec::<IndexTriplet>::with_capacity(parameters.len());
+        for parameter in &parameters {
+            let reference = parameter.parse::<IndexTriplet>().map_err(FreeFormElementError::InvalidReference)?;
+            if references.first().is_some_and(|first: &IndexTriplet| first.format() != reference.format()) {
+                return Err(FreeFormElementError::MixedReferenceFormats);
+            }
+            references.push(reference);
+        }
+
+        Ok(Surface { s_range, t_range, references, definition, body: FreeFormBody::new() })
+    }
+
+    pub fn resolve(&mut self, counts: &VertexCounts) -> Result<(), IndexError> {
+        for reference in self.references.iter_mut() {
+            *reference = reference.resolve(counts)?;
+        }
+        Ok(())
+    }
+}
+
+fn parse_value(parameters: &mut VecDeque<String>) -> Result<f32, FreeFormElementError> {
+    let value = parameters.pop_front().ok_or(FreeFormElementError::InvalidParameters)?;
+    value.parse::<f32>().ok().ok_or(FreeFormElementError::InvalidParameters)
+}
+
+// Curve control points are plain indices. At least two are required
+fn parse_vertices(parameters: &VecDeque<String>) -> Result<Vec<Index>, FreeFormElementError> {
+    if parameters.len() < 2 {
+        return Err(FreeFormElementError::NotEnoughReferences);
+    }
+
+    let mut vertices = Vec::<Index>::with_capacity(parameters.len());
+    for parameter in parameters {
+        let reference = parameter.parse::<IndexTriplet>().map_err(FreeFormElementError::InvalidReference)?;
+        if reference.format() != TripletFormat::Vertex {
+            return Err(FreeFormElementError::InvalidReferenceFormat);
+        }
+        vertices.push(reference.vertex);
+    }
+    Ok(vertices)
+}
@@ -30,6 +30,19 @@ pub enum FreeFormType {
    Taylor
 }
 
+impl FreeFormType {
+    // Keyword used by cstype
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            FreeFormType::BasisMatrix(_) => keywords::CURVE_TYPE_BMATRIX,
+            FreeFormType::Bezier         => keywords::CURVE_TYPE_BEZIER,
+            FreeFormType::BSpline        => keywords::CURVE_TYPE_BSPLINE,
+            FreeFormType::Cardinal       => keywords::CURVE_TYPE_CARDINAL,
+            FreeFormType::Taylor         => keywords::CURVE_TYPE_TAYLOR
+        }
+    }
+}
+
 impl FromStr for FreeFormType {
     type Err = FreeFormTypesErrors;
 
@@ -12,13 +12,13 @@
  *                      * Degree u required ✓
  *                      * Degree v only required for surfaces ✓
  *                      * For cardinal curve any value other than 3 is ignored ✓
- *              Elements 
- *                  - Control Points
- *                  - 3D curves need parameter range
- *              Body Statements
- *                  - Global Parameters or Knot Vector
- *                  - Only valid when appearing between element and end statments
- *                  - Explicit "End" Statement
+ *              Elements ✓
+ *                  - Control Points ✓
+ *                  - 3D curves need parameter range ✓
+ *              Body Statements ✓
+ *                  - Global Parameters or Knot Vector ✓
+ *                  - Only valid when appearing between element and end statments ✓
+ *                  - Explicit "End" Statement ✓
  *                      * At this step validation is done
  *          Additional Requirements for Basis Matrix:
  *              Attributes (State-Setting)
@@ -35,6 +35,10 @@
 
 pub mod freeform_types;
 pub mod freeform_definition;
+pub mod freeform_elements;
+pub mod body_statements;
+pub mod connection;
+pub mod evaluation;
 pub mod basis_matrix;
 pub mod uv_pair;
 
@@ -65,17 +65,11 @@ impl UVPair {
     }
 
     pub fn is_curve(&self) -> bool {
-        match self {
-            Self::Curve(_) => true,
-            _              => false
-        }
+        matches!(self, Self::Curve(_))
     }
 
     pub fn is_surface(&self) -> bool {
-        match self {
-            Self::Surface(_, _) => true,
-            _                   => false
-        }
+        matches!(self, Self::Surface(_, _))
     }
 }
 
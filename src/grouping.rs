@@ -0,0 +1,112 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Grouping
+ *      State-setting statements that organize elements
+ *          - Group Name        "g name1 name2 ..."
+ *              * An element can belong to several groups
+ *              * Without any names, the group is "default"
+ *          - Object Name       "o name"
+ *          - Smoothing Group   "s group_number"
+ *              * "off" and 0 both turn smoothing off
+ *          - Merging Group     "mg group_number res"
+ *              * Resolution is required unless the group is "off" or 0
+ *              * Resolution must be greater than 0
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::collections::VecDeque;
+
+use crate::keywords;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GroupingError {
+    InvalidParameters,          // Wrong number of parameters
+    InvalidGroupNumber,         // Smoothing and merging groups must be positive integers, or "off"
+    InvalidResolution,          // Merging resolution is missing, or isn't a number greater than 0
+    InvalidKeyword              // Keyword isn't a grouping statement
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct MergingGroup {
+    pub group:      u32,
+    pub resolution: f32         // Maximum distance between surfaces that are merged
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Grouping {
+    pub groups:          Vec<String>,
+    pub object:          Option<String>,
+    pub smoothing_group: Option<u32>,           // None when smoothing is off
+    pub merging_group:   Option<MergingGroup>   // None when merging is off
+}
+
+impl Default for Grouping {
+    fn default() -> Self {
+        Grouping { groups: vec![keywords::DEFAULT_GROUP.to_owned()], object: None, smoothing_group: None, merging_group: None }
+    }
+}
+
+impl Grouping {
+    pub fn new() -> Grouping {
+        Grouping::default()
+    }
+
+    pub fn apply(&mut self, keyword: &str, parameters: &VecDeque<String>) -> Result<(), GroupingError> {
+        match keyword {
+            keywords::GROUP_NAME      => {
+                self.groups = if parameters.is_empty() {
+                    vec![keywords::DEFAULT_GROUP.to_owned()]
+                } else {
+                    parameters.iter().cloned().collect()
+                };
+            }
+            keywords::OBJECT_NAME     => {
+                if parameters.len() != 1 {
+                    return Err(GroupingError::InvalidParameters);
+                }
+                self.object = parameters.front().cloned();
+            }
+            keywords::SMOOTHING_GROUP => {
+                if parameters.len() != 1 {
+                    return Err(GroupingError::InvalidParameters);
+                }
+                self.smoothing_group = Grouping::parse_group_number(&parameters[0])?;
+            }
+            keywords::MERGING_GROUP   => {
+                let group = Grouping::parse_group_number(parameters.front().ok_or(GroupingError::InvalidParameters)?)?;
+                self.merging_group = match group {
+                    Some(group) => {
+                        if parameters.len() != 2 {
+                            return Err(GroupingError::InvalidResolution);
+                        }
+                        let resolution = parameters[1].parse::<f32>().ok().filter(|resolution| *resolution > 0.0).ok_or(GroupingError::InvalidResolution)?;
+                        Some(MergingGroup { group, resolution })
+                    }
+                    None if parameters.len() <= 2 => None,
+                    None                          => return Err(GroupingError::InvalidParameters)
+                };
+            }
+            _                         => return Err(GroupingError::InvalidKeyword)
+        }
+        Ok(())
+    }
+
+    pub fn in_group(&self, name: &str) -> bool {
+        self.groups.iter().any(|group| group == name)
+    }
+
+    // "off" and 0 are the same
+    fn parse_group_number(parameter: &str) -> Result<Option<u32>, GroupingError> {
+        if parameter == keywords::STATE_OFF {
+            return Ok(None);
+        }
+
+        match parameter.parse::<u32>().ok().ok_or(GroupingError::InvalidGroupNumber)? {
+            0     => Ok(None),
+            group => Ok(Some(group))
+        }
+    }
+}
@@ -10,15 +10,20 @@
  * 
  *      This helps ensure that's obvious
  * 
+ *      Relative indices depend on the size of the buffer when the element was read,
+ *      so the interpreter resolves them to absolute indices as soon as they're parsed.
+ * 
  * ------------------------------------------------------------------------------------*/
 
+use std::fmt;
 use std::rc::Rc;
 
 type BufferObject<T> = Vec<Rc<T>>;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum IndexError {
-    IndexIsZero    // Only value index cannot be is zero, negative indices are relative, positive indices are absolute
+    IndexIsZero,   // Only value index cannot be is zero, negative indices are relative, positive indices are absolute
+    OutOfBounds    // Index points outside of the buffer it references
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -32,6 +37,36 @@ impl Index {
         Ok( Index(value) )
     }
 
+    // Create an absolute index from a zero based position
+    pub fn absolute(position: usize) -> Index {
+        Index(position as isize + 1)
+    }
+
+    pub fn is_relative(&self) -> bool {
+        self.0 < 0
+    }
+
+    // Get the zero based absolute position of the index in a buffer of the given length.
+    pub fn resolve(&self, length: usize) -> Result<usize, IndexError> {
+        let position = if self.0 > 0 {
+            (self.0 - 1) as usize
+        } else {
+            // Relative indices count back from the end of the buffer, -1 being the last element
+            length.checked_sub(self.0.unsigned_abs()).ok_or(IndexError::OutOfBounds)?
+        };
+
+        if position < length {
+            Ok(position)
+        } else {
+            Err(IndexError::OutOfBounds)
+        }
+    }
+
+    // Get an absolute copy of the index, relative to a buffer of the given length.
+    pub fn to_absolute(self, length: usize) -> Result<Index, IndexError> {
+        Ok(Index::absolute(self.resolve(length)?))
+    }
+
     pub fn as_isize(&self) -> isize {
         // This should hopefully never be possible.
         if self.0 == 0 {
@@ -50,4 +85,11 @@ impl Default for Index {
     fn default() -> Self { 
         Index(1isize)
     }
-}
\ No newline at end of file
+}
+
+// Written as it appears in an OBJ file
+impl fmt::Display for Index {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
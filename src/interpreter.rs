@@ -2,13 +2,591 @@
 
  * Author: Zatarita
  * Last Edit: Zatarita (5/11/2023)
- * 
+ *
  * OBJ Interpreter
  *  Responsible for handeling parsed data from parser.rs
- * 
+ *
+ *  OBJ files are a state machine. Vertex data is appended to the vertex buffer,
+ *  state-setting statements (grouping, display/render attributes, free-form attributes)
+ *  apply to every element that follows them, and free-form elements own the body
+ *  statements up until their "end" statement.
+ *
+ *  Relative indices are resolved as elements are read, since they depend on the
+ *  size of the vertex buffer at that moment.
+ *
+ *  The state machine sends what it reads to an output. The interpreter's output builds
+ *  the model, and visitors are driven by the same state machine. (see visitor.rs)
+ *
+ *  Errors are reported with the span of the statement that caused them.
+ *
  * ------------------------------------------------------------------------------------*/
 
- use crate::parser;
- use crate::keywords;
- use crate::vertex_buffer;
- use crate::index;
\ No newline at end of file
+use std::rc::Rc;
+use std::fs::File;
+use std::path::Path;
+use std::io::{BufRead, BufReader};
+use std::collections::VecDeque;
+
+use crate::parser::{ObjLine, ObjLineRef, ObjParser, LineSource};
+use crate::parallel::ParsedStatement;
+use crate::keywords::{self, Keyword};
+use crate::vertex_data::{Vertex, TextureCoordinate, VertexNormal, ParameterSpaceVertex};
+use crate::vertex_buffer::{VertexCounts, VertexBufferError};
+use crate::index::IndexError;
+use crate::element::{Element, ElementType, Geometry};
+use crate::grouping::{Grouping, GroupingError};
+use crate::render_state::{RenderState, RenderStateError};
+use crate::polygonal_geometry::polygonal_elements::{Point, Line, Face, PolygonalElementError};
+use crate::model::ObjModel;
+use crate::diagnostics::Diagnostic;
+use crate::options::{ParseOptions, Deviation};
+use crate::error::{ObjError, IoError};
+use crate::span::{Span, Spanned, WithSpan};
+use crate::freeform_geometry::freeform_definition::{FreeFormDefinition, FreeFormDefinitionError, FreeFormValidationExceptions, FreeFormState};
+use crate::freeform_geometry::freeform_elements::{Curve, Curve2D, Surface, FreeFormElementError};
+use crate::freeform_geometry::body_statements::{BodyContext, BodyStatementError};
+use crate::freeform_geometry::connection::{Connection, ConnectionError};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum InterpreterError {
+    VertexData(VertexBufferError),
+    FreeFormDefinition(FreeFormDefinitionError),
+    FreeFormValidation(FreeFormValidationExceptions),
+    PolygonalElement(PolygonalElementError),
+    FreeFormElement(FreeFormElementError),
+    BodyStatement(BodyStatementError),
+    Connection(ConnectionError),
+    Grouping(GroupingError),
+    RenderState(RenderStateError),
+    Index(IndexError),
+    UnknownKeyword,                 // Keyword isn't part of the specification
+    UnexpectedKeyword,              // Keyword is part of the specification, but can't start a statement
+    BodyStatementOutsideElement,    // Body statements are only valid between a free-form element and "end"
+    UnexpectedEnd,                  // "end" without a free-form element
+    MissingEnd,                     // Free-form element wasn't closed before the next element, or the end of the file
+    IO(IoError)                     // Line couldn't be read. (ex: it isn't valid UTF-8)
+}
+
+// Where the state machine sends what it reads. Hooks the model doesn't need do nothing by default
+#[allow(unused_variables)]
+pub(crate) trait InterpreterOutput {
+    // Size of each buffer so far. Relative indices are resolved against these
+    fn counts(&self) -> VertexCounts;
+    fn vertex(&mut self, vertex: Vertex);
+    fn texture_coordinate(&mut self, coordinate: TextureCoordinate);
+    fn normal(&mut self, normal: VertexNormal);
+    fn parameter_space_vertex(&mut self, vertex: ParameterSpaceVertex);
+    // Polygonal elements, and free-form elements once "end" closes them
+    fn element(&mut self, element: Element);
+    fn free_form_begin(&mut self, element: &Element) {}
+    fn body_statement(&mut self, keyword: &Keyword, parameters: &VecDeque<String>) {}
+    fn group(&mut self, grouping: &Grouping) {}
+    fn use_material(&mut self, material: &str) {}
+    fn material_library(&mut self, line: ObjLine);
+    fn connection(&mut self, connection: Connection, line: ObjLine);
+    // Every other statement. (ex: free-form attributes, display/render attributes)
+    fn statement(&mut self, line: ObjLine) {}
+    fn warning(&mut self, warning: Spanned<Deviation>);
+}
+
+// Everything that is applied to the elements that follow it
+#[derive(Debug, Clone, Default)]
+pub(crate) struct InterpreterState {
+    options:        ParseOptions,
+    free_form:      FreeFormState,
+    definition:     Option<Rc<FreeFormDefinition>>,  // Cached definition, shared until an attribute changes
+    grouping:       Rc<Grouping>,
+    render_state:   Rc<RenderState>,
+    open_element:   Option<Element>,                 // Free-form element waiting for its "end" statement
+    open_span:      Span,                            // Where the open free-form element was read
+    curve_2d_count: usize,                           // 2D curves read so far, referenced by trim, hole, scrv and con
+    surface_count:  usize                            // Surfaces read so far, referenced by con
+}
+
+// Output that builds the model
+#[derive(Debug, Clone, Default)]
+struct ModelBuilder {
+    model:       ObjModel,
+    connections: Vec<Span>                           // Where each connection was read, they're validated at the end
+}
+
+pub struct ObjInterpreter<R: BufRead = BufReader<File>> {
+    parser: ObjParser<R>,
+    state:  InterpreterState,
+    output: ModelBuilder
+}
+
+impl ObjInterpreter {
+    // Loads and interprets a file.
+    // Material libraries are loaded relative to the file.
+    pub fn load(path: &str) -> Result<ObjModel, ObjError> {
+        ObjInterpreter::load_with_options(path, ParseOptions::default())
+    }
+
+    // Loads a file, skipping statements that can't be read. (see interpret_with_diagnostics)
+    // Only fails if the file can't be opened. Material libraries aren't loaded.
+    pub fn load_with_diagnostics(path: &str, options: ParseOptions) -> Result<(ObjModel, Vec<Diagnostic>), ObjError> {
+        let parser = ObjParser::new(path)?;
+        Ok(ObjInterpreter::with_options(parser, options).interpret_with_diagnostics())
+    }
+
+    pub fn load_with_options(path: &str, options: ParseOptions) -> Result<ObjModel, ObjError> {
+        let parser = ObjParser::new(path)?;
+        let mut model = ObjInterpreter::with_options(parser, options).interpret()?;
+
+        model.load_materials_with_options(Path::new(path).parent().unwrap_or(Path::new("")), options);
+        Ok(model)
+    }
+}
+
+impl<R: BufRead> ObjInterpreter<R> {
+    pub fn new(parser: ObjParser<R>) -> ObjInterpreter<R> {
+        ObjInterpreter::with_options(parser, ParseOptions::default())
+    }
+
+    pub fn with_options(mut parser: ObjParser<R>, options: ParseOptions) -> ObjInterpreter<R> {
+        parser.set_latin1_fallback(options.latin1_text);
+        ObjInterpreter { parser, state: InterpreterState::new(options), output: ModelBuilder::default() }
+    }
+
+    // Consume every line from the parser and produce the final model
+    pub fn interpret(mut self) -> Result<ObjModel, Spanned<InterpreterError>> {
+        self.state.interpret_all(&mut self.parser, &mut self.output)?;
+        self.finish()
+    }
+
+    // Consume every line from another source, rather than the parser. (ex: a mapped file, see mapped.rs)
+    pub(crate) fn interpret_lines<S: LineSource>(mut self, lines: &mut S) -> Result<ObjModel, Spanned<InterpreterError>> {
+        self.state.interpret_all(lines, &mut self.output)?;
+        self.finish()
+    }
+
+    // Consume every line from the parser, skipping statements that can't be read.
+    // Returns what could be read, and every problem found along the way in file order.
+    pub fn interpret_with_diagnostics(mut self) -> (ObjModel, Vec<Diagnostic>) {
+        let mut diagnostics = Vec::<Diagnostic>::new();
+
+        loop {
+            let element_type = self.parser.get_next_keyword().and_then(|keyword| ElementType::from_keyword(keyword.as_str()));
+
+            // An element while a free-form element is open. Close it so the element can still be read
+            if self.state.open_element.is_some() && element_type.is_some() {
+                diagnostics.push(Diagnostic::error(Spanned::new(InterpreterError::MissingEnd, self.state.open_span)));
+                if let Err(error) = self.state.interpret_end(&mut self.output) {
+                    diagnostics.push(Diagnostic::error(Spanned::new(error, self.state.open_span)));
+                }
+            }
+
+            match self.state.interpret_next(&mut self.parser, &mut self.output) {
+                Some(Err(error)) => {
+                    diagnostics.push(Diagnostic::error(error));
+                    // The body of a free-form element that couldn't be read goes with it
+                    if element_type.is_some_and(|element_type| element_type.is_free_form()) {
+                        self.skip_body();
+                    }
+                }
+                Some(Ok(()))     => (),
+                // A line that couldn't be read. The rest of the file is still read, unless the error ended the stream
+                None             => match self.parser.take_error() {
+                    Some(error) => diagnostics.push(Diagnostic::error(error.map(|error| InterpreterError::IO(error.into())))),
+                    None        => break
+                }
+            }
+        }
+
+        if self.state.open_element.is_some() {
+            diagnostics.push(Diagnostic::error(Spanned::new(InterpreterError::MissingEnd, self.state.open_span)));
+            if let Err(error) = self.state.interpret_end(&mut self.output) {
+                diagnostics.push(Diagnostic::error(Spanned::new(error, self.state.open_span)));
+            }
+        }
+        let ModelBuilder { model, connections } = self.output;
+        for (connection, span) in model.connections.iter().zip(&connections) {
+            if let Err(error) = connection.validate(&model) {
+                diagnostics.push(Diagnostic::error(Spanned::new(InterpreterError::Connection(error), *span)));
+            }
+        }
+
+        diagnostics.extend(model.warnings.iter().copied().map(Diagnostic::warning));
+        diagnostics.sort_by_key(|diagnostic| (diagnostic.span.start_line, diagnostic.span.start_column));
+        (model, diagnostics)
+    }
+
+    // Skip body statements, up to and including "end"
+    fn skip_body(&mut self) {
+        while let Some(line) = self.parser.peek_line_ref() {
+            match line.keyword() {
+                Some(Keyword::End)            => {
+                    self.parser.skip_line();
+                    break;
+                }
+                None                          |
+                Some(Keyword::ParameterValue) |
+                Some(Keyword::OuterTrimLoop)  |
+                Some(Keyword::InnerTrimLoop)  |
+                Some(Keyword::SpecialCurve)   |
+                Some(Keyword::SpecialPoint)   => self.parser.skip_line(),
+                _                             => break
+            }
+        }
+    }
+
+    // Get the resulting model. Every free-form element must have been closed by an "end" statement.
+    // Connections can reference surfaces and 2D curves anywhere in the file, so they're checked last.
+    pub fn finish(self) -> Result<ObjModel, Spanned<InterpreterError>> {
+        self.state.finish()?;
+
+        let ModelBuilder { model, connections } = self.output;
+        for (connection, span) in model.connections.iter().zip(&connections) {
+            connection.validate(&model).map_err(InterpreterError::Connection).with_span(*span)?;
+        }
+        Ok(model)
+    }
+
+    pub fn interpret_line(&mut self, line: ObjLine) -> Result<(), Spanned<InterpreterError>> {
+        self.state.interpret_line(line, &mut self.output)
+    }
+
+    // Statement parsed ahead of time on another thread. (see parallel.rs)
+    // Relative indices are resolved against the buffer as it is now.
+    pub(crate) fn interpret_parsed(&mut self, statement: ParsedStatement, span: Span) -> Result<(), Spanned<InterpreterError>> {
+        let (state, output) = (&mut self.state, &mut self.output);
+        let result = match statement {
+            ParsedStatement::Vertex(vertex)                 => { output.vertex(vertex); Ok(()) }
+            ParsedStatement::TextureCoordinate(coordinate)  => { output.texture_coordinate(coordinate); Ok(()) }
+            ParsedStatement::Normal(normal)                 => { output.normal(normal); Ok(()) }
+            ParsedStatement::ParameterSpaceVertex(vertex)   => { output.parameter_space_vertex(vertex); Ok(()) }
+            ParsedStatement::Point(point)                   => state.push_polygonal_element(Ok(point), Point::resolve, Geometry::Point, output),
+            ParsedStatement::Line(line)                     => state.push_polygonal_element(Ok(line), Line::resolve, Geometry::Line, output),
+            ParsedStatement::Face(face)                     => state.push_polygonal_element(Ok(face), Face::resolve, Geometry::Face, output),
+            ParsedStatement::Statement(line)                => return state.interpret_line(line, output),
+            ParsedStatement::Warning(deviation)             => {
+                output.warning(Spanned::new(deviation, span));
+                Ok(())
+            }
+            ParsedStatement::Error(error)                   => Err(error)
+        };
+        state.with_span(result, span)
+    }
+}
+
+impl InterpreterOutput for ModelBuilder {
+    fn counts(&self) -> VertexCounts {
+        self.model.vertex_buffer.counts()
+    }
+    fn vertex(&mut self, vertex: Vertex) {
+        self.model.vertex_buffer.add_vertex(vertex);
+    }
+    fn texture_coordinate(&mut self, coordinate: TextureCoordinate) {
+        self.model.vertex_buffer.add_texture_coordinate(coordinate);
+    }
+    fn normal(&mut self, normal: VertexNormal) {
+        self.model.vertex_buffer.add_normal(normal);
+    }
+    fn parameter_space_vertex(&mut self, vertex: ParameterSpaceVertex) {
+        self.model.vertex_buffer.add_parameter_space_vertex(vertex);
+    }
+    fn element(&mut self, element: Element) {
+        self.model.push_element(element);
+    }
+    fn material_library(&mut self, line: ObjLine) {
+        self.model.material_libraries.extend(line.parameters);
+    }
+    fn connection(&mut self, connection: Connection, line: ObjLine) {
+        self.model.connections.push(connection);
+        self.connections.push(line.span);
+    }
+    fn warning(&mut self, warning: Spanned<Deviation>) {
+        self.model.warnings.push(warning);
+    }
+}
+
+impl InterpreterState {
+    pub(crate) fn new(options: ParseOptions) -> InterpreterState {
+        InterpreterState { options, ..InterpreterState::default() }
+    }
+
+    // Interpret every line, stopping at the first error. Every free-form element must be closed
+    pub(crate) fn interpret_all<S: LineSource, O: InterpreterOutput>(&mut self, lines: &mut S, output: &mut O) -> Result<(), Spanned<InterpreterError>> {
+        while let Some(result) = self.interpret_next(lines, output) {
+            result?;
+        }
+
+        if let Some(error) = lines.take_error() {
+            return Err(error.map(|error| InterpreterError::IO(error.into())));
+        }
+        self.finish()
+    }
+
+    // Interpret the next line. None at the end of the stream
+    pub(crate) fn interpret_next<S: LineSource, O: InterpreterOutput>(&mut self, lines: &mut S, output: &mut O) -> Option<Result<(), Spanned<InterpreterError>>> {
+        let (line, latin1) = lines.next_line()?;
+        // Warnings come before the statement they were found in
+        if latin1 {
+            output.warning(Spanned::new(Deviation::Latin1Text, line.span));
+        }
+        Some(self.interpret_line_ref(line, output))
+    }
+
+    fn interpret_line_ref<O: InterpreterOutput>(&mut self, line: ObjLineRef, output: &mut O) -> Result<(), Spanned<InterpreterError>> {
+        match line.keyword {
+            // Vertex data is parsed straight from the line buffer
+            Some(keyword @ (keywords::VERTEX | keywords::TEXTURE_COORDINATE | keywords::VERTEX_NORMAL | keywords::PARAMETER_SPACE_VERTEX))
+                => self.interpret_vertex_data(keyword, line.parameters, line.span, output).with_span(line.span),
+            _   => self.interpret_line(line.to_line(), output)
+        }
+    }
+
+    // The open free-form element must have been closed by an "end" statement
+    pub(crate) fn finish(&self) -> Result<(), Spanned<InterpreterError>> {
+        match self.open_element {
+            Some(_) => Err(Spanned::new(InterpreterError::MissingEnd, self.open_span)),
+            None    => Ok(())
+        }
+    }
+
+    pub(crate) fn interpret_line<O: InterpreterOutput>(&mut self, line: ObjLine, output: &mut O) -> Result<(), Spanned<InterpreterError>> {
+        let span = line.span;
+        let result = self.interpret_statement(line, output);
+        self.with_span(result, span)
+    }
+
+    fn with_span(&self, result: Result<(), InterpreterError>, span: Span) -> Result<(), Spanned<InterpreterError>> {
+        match result {
+            // Reported where the unclosed, or invalid, element starts
+            Err(error @ (InterpreterError::MissingEnd | InterpreterError::FreeFormValidation(_))) => Err(Spanned::new(error, self.open_span)),
+            result                                                                                => result.with_span(span)
+        }
+    }
+
+    fn interpret_statement<O: InterpreterOutput>(&mut self, mut line: ObjLine, output: &mut O) -> Result<(), InterpreterError> {
+        // Comments and empty lines
+        let keyword = match line.keyword.take() {
+            Some(keyword) => keyword,
+            None          => return Ok(())
+        };
+
+        if let Some(deviation) = self.options.repair(&keyword, &mut line.parameters) {
+            output.warning(Spanned::new(deviation, line.span));
+        }
+
+        match &keyword {
+            // Vertex data
+            Keyword::Vertex            |
+            Keyword::TextureCoordinate |
+            Keyword::VertexNormal      |
+            Keyword::ParameterSpaceVertex  => self.interpret_vertex_data(keyword.as_str(), &Vec::from(line.parameters).join(" "), line.span, output),
+            // Free-form attributes
+            Keyword::CurveSurfaceType |
+            Keyword::Degree           |
+            Keyword::BasisMatrix      |
+            Keyword::StepSize              => {
+                self.interpret_free_form_attribute(keyword.as_str(), &mut line.parameters.clone())?;
+                line.keyword = Some(keyword);
+                output.statement(line);
+                Ok(())
+            }
+            // Elements
+            Keyword::Point                 => self.interpret_element(ElementType::Point, line.parameters, line.span, output),
+            Keyword::Line                  => self.interpret_element(ElementType::Line, line.parameters, line.span, output),
+            Keyword::Face                  => self.interpret_element(ElementType::Face, line.parameters, line.span, output),
+            Keyword::Curve                 => self.interpret_element(ElementType::Curve, line.parameters, line.span, output),
+            Keyword::Curve2D               => self.interpret_element(ElementType::Curve2D, line.parameters, line.span, output),
+            Keyword::Surface               => self.interpret_element(ElementType::Surface, line.parameters, line.span, output),
+            // Free-form body statements
+            Keyword::ParameterValue |
+            Keyword::OuterTrimLoop  |
+            Keyword::InnerTrimLoop  |
+            Keyword::SpecialCurve   |
+            Keyword::SpecialPoint          => self.interpret_body_statement(&keyword, &line.parameters, output),
+            Keyword::End                   => self.interpret_end(output),
+            // Connectivity
+            Keyword::Connection            => {
+                let connection = Connection::from(&line.parameters, self.surface_count, self.curve_2d_count).map_err(InterpreterError::Connection)?;
+                line.keyword = Some(keyword);
+                output.connection(connection, line);
+                Ok(())
+            }
+            // Grouping
+            Keyword::GroupName      |
+            Keyword::SmoothingGroup |
+            Keyword::MergingGroup   |
+            Keyword::ObjectName            => {
+                Rc::make_mut(&mut self.grouping).apply(keyword.as_str(), &line.parameters).map_err(InterpreterError::Grouping)?;
+                output.group(&self.grouping);
+                Ok(())
+            }
+            // Display/render attributes
+            Keyword::MaterialName          => {
+                Rc::make_mut(&mut self.render_state).apply(keyword.as_str(), &line.parameters).map_err(InterpreterError::RenderState)?;
+                output.use_material(self.render_state.material.as_deref().unwrap_or_default());
+                Ok(())
+            }
+            Keyword::MaterialLibrary       => {
+                Rc::make_mut(&mut self.render_state).apply(keyword.as_str(), &line.parameters).map_err(InterpreterError::RenderState)?;
+                line.keyword = Some(keyword);
+                output.material_library(line);
+                Ok(())
+            }
+            Keyword::BevelInterpolation    |
+            Keyword::ColorInterpolation    |
+            Keyword::DissolveInterpolation |
+            Keyword::LevelOfDetail         |
+            Keyword::MapLib                |
+            Keyword::UseMap                |
+            Keyword::ShadowCasting         |
+            Keyword::RayTracing            |
+            Keyword::CurveApproximation    |
+            Keyword::SurfaceApproximation  => {
+                Rc::make_mut(&mut self.render_state).apply(keyword.as_str(), &line.parameters).map_err(InterpreterError::RenderState)?;
+                line.keyword = Some(keyword);
+                output.statement(line);
+                Ok(())
+            }
+            // Keywords that are only valid as parameters
+            Keyword::Unknown(keyword) if keywords::validate_keyword(keyword) => Err(InterpreterError::UnexpectedKeyword),
+            Keyword::Unknown(keyword) if self.options.skips_keyword(keyword) => {
+                output.warning(Spanned::new(Deviation::UnknownKeyword, line.span));
+                Ok(())
+            }
+            Keyword::Unknown(_)            => Err(InterpreterError::UnknownKeyword)
+        }
+    }
+
+    fn interpret_vertex_data<O: InterpreterOutput>(&mut self, keyword: &str, parameters: &str, span: Span, output: &mut O) -> Result<(), InterpreterError> {
+        let parsed = match keyword {
+            keywords::VERTEX             => Vertex::parse(parameters).or_else(|| {
+                // Vertices written with a color keep only their position, when the options allow it
+                let vertex = self.options.vertex_position(parameters).and_then(Vertex::parse)?;
+                output.warning(Spanned::new(Deviation::VertexColor, span));
+                Some(vertex)
+            }).map(|vertex| output.vertex(vertex)),
+            keywords::TEXTURE_COORDINATE => TextureCoordinate::parse(parameters).map(|coordinate| output.texture_coordinate(coordinate)),
+            keywords::VERTEX_NORMAL      => VertexNormal::parse(parameters).map(|normal| output.normal(normal)),
+            _                            => ParameterSpaceVertex::parse(parameters).map(|vertex| output.parameter_space_vertex(vertex))
+        };
+        parsed.ok_or(InterpreterError::VertexData(VertexBufferError::InterpreterError))
+    }
+
+    fn interpret_free_form_attribute(&mut self, keyword: &str, parameters: &mut VecDeque<String>) -> Result<(), InterpreterError> {
+        self.free_form.apply(keyword, parameters).map_err(InterpreterError::FreeFormDefinition)?;
+        // The definition changed, elements that follow get a new one.
+        self.definition = None;
+        Ok(())
+    }
+
+    fn interpret_element<O: InterpreterOutput>(&mut self, element_type: ElementType, parameters: VecDeque<String>, span: Span, output: &mut O) -> Result<(), InterpreterError> {
+        if self.open_element.is_some() {
+            return Err(InterpreterError::MissingEnd);
+        }
+
+        let counts = output.counts();
+        let geometry = match element_type {
+            ElementType::Point   => return self.push_polygonal_element(Point::from(&parameters), Point::resolve, Geometry::Point, output),
+            ElementType::Line    => return self.push_polygonal_element(Line::from(&parameters), Line::resolve, Geometry::Line, output),
+            ElementType::Face    => return self.push_polygonal_element(Face::from(&parameters), Face::resolve, Geometry::Face, output),
+            ElementType::Curve   => {
+                let curve = Curve::from(&parameters, self.current_definition()?);
+                Geometry::Curve(free_form_element(curve, Curve::resolve, &counts)?)
+            }
+            ElementType::Curve2D => {
+                let curve = Curve2D::from(&parameters, self.current_definition()?);
+                let curve = free_form_element(curve, Curve2D::resolve, &counts)?;
+                self.curve_2d_count += 1;
+                Geometry::Curve2D(curve)
+            }
+            ElementType::Surface => {
+                let surface = Surface::from(&parameters, self.current_definition()?);
+                let surface = free_form_element(surface, Surface::resolve, &counts)?;
+                self.surface_count += 1;
+                Geometry::Surface(surface)
+            }
+        };
+
+        let element = self.element(geometry);
+        output.free_form_begin(&element);
+        self.open_element = Some(element);
+        self.open_span = span;
+        Ok(())
+    }
+
+    fn interpret_body_statement<O: InterpreterOutput>(&mut self, keyword: &Keyword, parameters: &VecDeque<String>, output: &mut O) -> Result<(), InterpreterError> {
+        let element = self.open_element.as_mut().ok_or(InterpreterError::BodyStatementOutsideElement)?;
+        let mut context = BodyContext {
+            is_surface:               false,
+            curve_2d_count:           self.curve_2d_count,
+            parameter_space_vertices: output.counts().parameter_space_vertices
+        };
+
+        let body = match &mut element.geometry {
+            Geometry::Curve(curve)     => &mut curve.body,
+            Geometry::Curve2D(curve)   => &mut curve.body,
+            Geometry::Surface(surface) => {
+                context.is_surface = true;
+                &mut surface.body
+            }
+            _                          => return Err(InterpreterError::BodyStatementOutsideElement)
+        };
+        body.apply(keyword.as_str(), parameters, &context).map_err(InterpreterError::BodyStatement)?;
+        output.body_statement(keyword, parameters);
+        Ok(())
+    }
+
+    pub(crate) fn interpret_end<O: InterpreterOutput>(&mut self, output: &mut O) -> Result<(), InterpreterError> {
+        let element = self.open_element.take().ok_or(InterpreterError::UnexpectedEnd)?;
+
+        // The element is complete, so its definition can be validated
+        let definition = match &element.geometry {
+            Geometry::Curve(curve)     => &curve.definition,
+            Geometry::Curve2D(curve)   => &curve.definition,
+            Geometry::Surface(surface) => &surface.definition,
+            _                          => return Err(InterpreterError::UnexpectedEnd)
+        };
+        if let Err(error) = definition.validate() {
+            if !self.options.invalid_definitions {
+                return Err(InterpreterError::FreeFormValidation(error));
+            }
+            output.warning(Spanned::new(Deviation::InvalidDefinition, self.open_span));
+        }
+
+        output.element(element);
+        Ok(())
+    }
+
+    fn push_polygonal_element<ElementT, O: InterpreterOutput>(&mut self, element: Result<ElementT, PolygonalElementError>, resolve: fn(&mut ElementT, &VertexCounts) -> Result<(), IndexError>, geometry: fn(ElementT) -> Geometry, output: &mut O) -> Result<(), InterpreterError> {
+        if self.open_element.is_some() {
+            return Err(InterpreterError::MissingEnd);
+        }
+
+        let mut element = element.map_err(InterpreterError::PolygonalElement)?;
+        resolve(&mut element, &output.counts()).map_err(InterpreterError::Index)?;
+        output.element(self.element(geometry(element)));
+        Ok(())
+    }
+
+    // Elements share the state that was active when they were read
+    fn element(&self, geometry: Geometry) -> Element {
+        Element {
+            geometry,
+            grouping:     self.grouping.clone(),
+            render_state: self.render_state.clone()
+        }
+    }
+
+    // Free-form elements share the definition that was active when they were read.
+    fn current_definition(&mut self) -> Result<Rc<FreeFormDefinition>, InterpreterError> {
+        if let Some(definition) = &self.definition {
+            return Ok(definition.clone());
+        }
+
+        let definition = Rc::new(self.free_form.definition().map_err(InterpreterError::FreeFormDefinition)?);
+        self.definition = Some(definition.clone());
+        Ok(definition)
+    }
+}
+
+fn free_form_element<ElementT>(element: Result<ElementT, FreeFormElementError>, resolve: fn(&mut ElementT, &VertexCounts) -> Result<(), IndexError>, counts: &VertexCounts) -> Result<ElementT, InterpreterError> {
+    let mut element = element.map_err(InterpreterError::FreeFormElement)?;
+    resolve(&mut element, counts).map_err(InterpreterError::Index)?;
+    Ok(element)
+}
@@ -8,6 +8,10 @@
  * 
  * ------------------------------------------------------------------------------------*/
 
+use std::fmt;
+use std::str::FromStr;
+use std::convert::Infallible;
+
 
 /* --------------------------------------------------------------------------------------
  * OBJ File Keywords 
@@ -73,13 +77,65 @@ pub const CURVE_TYPE_TAYLOR:                                &str = "taylor";
 // Basis Matrix                     
 pub const BASIS_MATRIX_U:                                   &str = "u";
 pub const BASIS_MATRIX_V:                                   &str = "v";
+// Grouping & display/render attribute parameters
+pub const DEFAULT_GROUP:                                    &str = "default";
+pub const STATE_ON:                                         &str = "on";
+pub const STATE_OFF:                                        &str = "off";
+
+
+/* --------------------------------------------------------------------------------------
+ * MTL File Keywords
+ * ------------------------------------------------------------------------------------*/
+pub const NEW_MATERIAL:                                     &str = "newmtl";
+// Color & Illumination
+pub const AMBIENT_REFLECTIVITY:                             &str = "Ka";
+pub const DIFFUSE_REFLECTIVITY:                             &str = "Kd";
+pub const SPECULAR_REFLECTIVITY:                            &str = "Ks";
+pub const TRANSMISSION_FILTER:                              &str = "Tf";
+pub const ILLUMINATION_MODEL:                               &str = "illum";
+pub const DISSOLVE:                                         &str = "d";
+pub const SPECULAR_EXPONENT:                                &str = "Ns";
+pub const SHARPNESS:                                        &str = "sharpness";
+pub const OPTICAL_DENSITY:                                  &str = "Ni";
+// Color Forms
+pub const COLOR_SPECTRAL:                                   &str = "spectral";
+pub const COLOR_XYZ:                                        &str = "xyz";
+pub const DISSOLVE_HALO:                                    &str = "-halo";
+// Texture Maps
+pub const AMBIENT_MAP:                                      &str = "map_Ka";
+pub const DIFFUSE_MAP:                                      &str = "map_Kd";
+pub const SPECULAR_MAP:                                     &str = "map_Ks";
+pub const SPECULAR_EXPONENT_MAP:                            &str = "map_Ns";
+pub const DISSOLVE_MAP:                                     &str = "map_d";
+pub const ANTI_ALIASING:                                    &str = "map_aat";
+pub const DECAL:                                            &str = "decal";
+pub const DISPLACEMENT:                                     &str = "disp";
+pub const BUMP:                                             &str = "bump";
+pub const REFLECTION_MAP:                                   &str = "refl";
+// Texture Map Options
+pub const OPTION_BLEND_U:                                   &str = "-blendu";
+pub const OPTION_BLEND_V:                                   &str = "-blendv";
+pub const OPTION_COLOR_CORRECTION:                          &str = "-cc";
+pub const OPTION_CLAMP:                                     &str = "-clamp";
+pub const OPTION_RANGE:                                     &str = "-mm";
+pub const OPTION_OFFSET:                                    &str = "-o";
+pub const OPTION_SCALE:                                     &str = "-s";
+pub const OPTION_TURBULENCE:                                &str = "-t";
+pub const OPTION_TEXTURE_RESOLUTION:                        &str = "-texres";
+pub const OPTION_BUMP_MULTIPLIER:                           &str = "-bm";
+pub const OPTION_BOOST:                                     &str = "-boost";
+pub const OPTION_CHANNEL:                                   &str = "-imfchan";
+pub const OPTION_REFLECTION_TYPE:                           &str = "-type";
+// Option Values
+pub const OPTION_ON:                                        &str = "on";
+pub const OPTION_OFF:                                       &str = "off";
 
 
 /* --------------------------------------------------------------------------------------
  * Compliance
  * ------------------------------------------------------------------------------------*/
 
-pub const VALID_KEYWORDS: [&'static str; 48] = [
+pub const VALID_KEYWORDS: [&str; 48] = [
     VERTEX,
     TEXTURE_COORDINATE,
     VERTEX_NORMAL,
@@ -137,7 +193,34 @@ pub fn validate_keyword(keyword: &str) -> bool {
     true
 }
 
-pub const VALID_CURVE_TYPES: [&'static str; 5] = [
+pub const VALID_MATERIAL_KEYWORDS: [&str; 20] = [
+    NEW_MATERIAL,
+    AMBIENT_REFLECTIVITY,
+    DIFFUSE_REFLECTIVITY,
+    SPECULAR_REFLECTIVITY,
+    TRANSMISSION_FILTER,
+    ILLUMINATION_MODEL,
+    DISSOLVE,
+    SPECULAR_EXPONENT,
+    SHARPNESS,
+    OPTICAL_DENSITY,
+    AMBIENT_MAP,
+    DIFFUSE_MAP,
+    SPECULAR_MAP,
+    SPECULAR_EXPONENT_MAP,
+    DISSOLVE_MAP,
+    ANTI_ALIASING,
+    DECAL,
+    DISPLACEMENT,
+    BUMP,
+    REFLECTION_MAP
+];
+
+pub fn validate_material_keyword(keyword: &str) -> bool {
+    VALID_MATERIAL_KEYWORDS.contains(&keyword)
+}
+
+pub const VALID_CURVE_TYPES: [&str; 5] = [
     CURVE_TYPE_BMATRIX, 
     CURVE_TYPE_BEZIER,
     CURVE_TYPE_BSPLINE,
@@ -152,7 +235,7 @@ pub fn validate_curve(keyword: &str) -> bool {
     true
 }
 
-pub const VALID_BASIS_MATRIX_AXES: [&'static str; 2] = [
+pub const VALID_BASIS_MATRIX_AXES: [&str; 2] = [
     BASIS_MATRIX_U,
     BASIS_MATRIX_V
 ];
@@ -164,7 +247,7 @@ pub fn validate_basis_axis(keyword: &str) -> bool {
     true
 }
 
-pub const VALID_FREEFORM_APPROXIMATION_TECHNIQUES: [&'static str; 5] = [
+pub const VALID_FREEFORM_APPROXIMATION_TECHNIQUES: [&str; 5] = [
     CONSTANT_PARAMETRIC_SUBDIVISION,
     CONSTANT_SPATIAL_SUBDIVISION,
     CURVE_DEPENDANT_SUBDIVISION,
@@ -179,7 +262,7 @@ pub fn validate_approximation_techniques(keyword: &str) -> bool {
     true
 }
 
-pub const VALID_DISPLAY_RENDER_ATTRIBUTES: [&'static str; 12] = [
+pub const VALID_DISPLAY_RENDER_ATTRIBUTES: [&str; 12] = [
     BEVEL_INTERPOLATION,
     COLOR_INTERPOLATION,
     DISSOLVE_INTERPOLATION,
@@ -199,4 +282,112 @@ pub fn validate_display_render_attributes(keyword: &str) -> bool {
         return false;
     }
     true
-}
\ No newline at end of file
+}
+
+
+/* --------------------------------------------------------------------------------------
+ * Typed Keywords
+ *      Every keyword that can start a statement in an OBJ file.
+ *      Anything else, including parameter keywords and MTL statements, is kept as Unknown.
+ * ------------------------------------------------------------------------------------*/
+
+macro_rules! keyword_enum {
+    ( $( $variant:ident => $constant:ident ),* ) => {
+        #[derive(Debug, Clone, PartialEq, Eq, Hash)]
+        pub enum Keyword {
+            $( $variant, )*
+            Unknown(String)
+        }
+
+        impl Keyword {
+            pub fn as_str(&self) -> &str {
+                match self {
+                    $( Keyword::$variant => $constant, )*
+                    Keyword::Unknown(keyword) => keyword
+                }
+            }
+        }
+
+        impl FromStr for Keyword {
+            type Err = Infallible;
+
+            fn from_str(keyword: &str) -> Result<Self, Self::Err> {
+                Ok(match keyword {
+                    $( $constant => Keyword::$variant, )*
+                    _ => Keyword::Unknown(keyword.to_owned())
+                })
+            }
+        }
+    };
+}
+
+keyword_enum!(
+    Vertex                => VERTEX,
+    TextureCoordinate     => TEXTURE_COORDINATE,
+    VertexNormal          => VERTEX_NORMAL,
+    ParameterSpaceVertex  => PARAMETER_SPACE_VERTEX,
+    CurveSurfaceType      => CURVE_SURFACE_TYPE,
+    Degree                => DEGREE,
+    BasisMatrix           => BASIS_MATRIX,
+    StepSize              => STEP_SIZE,
+    Point                 => POINT,
+    Line                  => LINE,
+    Face                  => FACE,
+    Curve                 => CURVE,
+    Curve2D               => CURVE_2D,
+    Surface               => SURFACE,
+    ParameterValue        => PARAMETER_VALUE,
+    OuterTrimLoop         => OUTER_TRIM_LOOP,
+    InnerTrimLoop         => INNER_TRIM_LOOP,
+    SpecialCurve          => SPECIAL_CURVE,
+    SpecialPoint          => SPECIAL_POINT,
+    End                   => END,
+    Connection            => CONNECTION,
+    GroupName             => GROUP_NAME,
+    SmoothingGroup        => SMOOTHING_GROUP,
+    MergingGroup          => MERGING_GROUP,
+    ObjectName            => OBJECT_NAME,
+    BevelInterpolation    => BEVEL_INTERPOLATION,
+    ColorInterpolation    => COLOR_INTERPOLATION,
+    DissolveInterpolation => DISSOLVE_INTERPOLATION,
+    LevelOfDetail         => LEVEL_OF_DETAIL,
+    MapLib                => MAP_LIB,
+    UseMap                => USE_MAP,
+    MaterialName          => MATERIAL_NAME,
+    MaterialLibrary       => MATERIAL_LIBRARY,
+    ShadowCasting         => SHADOW_CASTING,
+    RayTracing            => RAY_TRACING,
+    CurveApproximation    => CURVE_APPROXIMATION,
+    SurfaceApproximation  => SURFACE_APPROXIMATION
+);
+
+impl Keyword {
+    pub fn is_unknown(&self) -> bool {
+        matches!(self, Keyword::Unknown(_))
+    }
+}
+
+impl From<&str> for Keyword {
+    fn from(keyword: &str) -> Self {
+        let Ok(keyword) = keyword.parse();
+        keyword
+    }
+}
+
+impl PartialEq<str> for Keyword {
+    fn eq(&self, other: &str) -> bool {
+        self.as_str() == other
+    }
+}
+
+impl PartialEq<&str> for Keyword {
+    fn eq(&self, other: &&str) -> bool {
+        self.as_str() == *other
+    }
+}
+
+impl fmt::Display for Keyword {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
@@ -1,4 +1,5 @@
 #![allow(dead_code)]
+#![allow(clippy::manual_is_multiple_of)]   // is_multiple_of needs Rust 1.87. "% n == 0" builds on older compilers
 
 pub mod vertex_data;
 pub mod keywords;
@@ -8,28 +9,70 @@ pub mod vertex_buffer;
 pub mod index;
 pub mod freeform_geometry;
 pub mod utility;
+pub mod element;
+pub mod grouping;
+pub mod render_state;
+pub mod polygonal_geometry;
+pub mod model;
+pub mod span;
+pub mod error;
+pub mod writer;
+pub mod mtl;
+pub mod parallel;
+pub mod visitor;
+pub mod options;
+pub mod diagnostics;
+#[cfg(feature = "memmap")]
+pub mod mapped;
 
-pub use crate::parser::{ObjLine, ObjParser};
+pub use crate::parser::{ObjLine, ObjLineRef, ObjParser};
+pub use crate::keywords::Keyword;
+pub use crate::span::{Span, Spanned};
+pub use crate::error::{ObjError, IoError};
 
 pub use crate::freeform_geometry::basis_matrix::matrix::{Matrix, MatrixError};
 pub use crate::freeform_geometry::basis_matrix::matrix_elements::{MatrixElements, MatrixColumn, MatrixRow};
 pub use crate::freeform_geometry::basis_matrix::basis_matrix_attributes::{BasisMatrixAttributes, BasisMatrixAttributesError};
 
-pub use crate::freeform_geometry::freeform_definition::{FreeFormDefinition, FreeFormDefinitionError, FreeFormValidationExceptions};
+pub use crate::freeform_geometry::freeform_definition::{FreeFormDefinition, FreeFormDefinitionError, FreeFormValidationExceptions, FreeFormState};
 pub use crate::freeform_geometry::freeform_types::{FreeFormType, FreeFormTypesErrors};
+pub use crate::freeform_geometry::freeform_elements::{Curve, Curve2D, Surface, FreeFormElementError};
+pub use crate::freeform_geometry::body_statements::{FreeFormBody, CurveReference, BodyContext, BodyStatementError};
+pub use crate::freeform_geometry::connection::{Connection, SurfaceCurve, ConnectionError};
+pub use crate::freeform_geometry::evaluation::{EvaluationError, HomogeneousPoint};
 
 pub use crate::freeform_geometry::uv_pair::UVPairError;
 pub use crate::freeform_geometry::Degree;
 pub use crate::freeform_geometry::basis_matrix::Step;
 
-pub use crate::vertex_buffer::{VertexBuffer, VertexBufferError};
+pub use crate::vertex_buffer::{VertexBuffer, VertexCounts, VertexBufferError};
 pub use crate::vertex_data::*;
 pub use crate::index::{Index, IndexError};
+pub use crate::element::{Element, ElementType, Geometry};
+pub use crate::grouping::{Grouping, MergingGroup, GroupingError};
+pub use crate::render_state::{RenderState, CurveApproximation, SurfaceApproximation, RenderStateError};
+pub use crate::polygonal_geometry::index_triplet::{IndexTriplet, IndexTripletError, TripletFormat};
+pub use crate::polygonal_geometry::polygonal_elements::{Point, Line, Face, PolygonalElementError};
+pub use crate::model::{ObjModel, UnresolvedLibrary};
+pub use crate::writer::ObjWriter;
+pub use crate::mtl::color::Color;
+pub use crate::mtl::texture_map::{TextureMap, TextureMapOptions, MapChannel, ReflectionType};
+pub use crate::mtl::material::{Material, MaterialError, Dissolve};
+pub use crate::mtl::material_library::MaterialLibrary;
+pub use crate::interpreter::{ObjInterpreter, InterpreterError};
+pub use crate::parallel::interpret_parallel;
+pub use crate::visitor::ObjVisitor;
+pub use crate::options::{ParseOptions, Deviation};
+pub use crate::diagnostics::{Diagnostic, DiagnosticCode, Severity};
+#[cfg(feature = "memmap")]
+pub use crate::mapped::MappedFile;
+pub use crate::utility::UtilityError;
 
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use std::io::Read;
 
     #[test]
     fn test_vertex_buffer_indexing() {
@@ -58,6 +101,7 @@ mod tests {
     }
 
     #[test]
+    #[allow(clippy::clone_on_copy)]
     fn test_free_form_validation() {
         // Construct a matrix with a degree as a curve, but the matrix step as a surface.
         let rational = false;
@@ -92,6 +136,29 @@ mod tests {
         let cardinal = FreeFormDefinition::new(FreeFormType::Cardinal, false, Degree::Curve(3));
         assert_eq!(cardinal.validate(), Ok(()));
 
+        // Definitions are validated when "end" closes the element, and reported where the element starts
+        struct Warnings(Vec<Spanned<Deviation>>);
+        impl ObjVisitor for Warnings {
+            fn warning(&mut self, warning: Spanned<Deviation>) { self.0.push(warning); }
+        }
+
+        let vertices = "v 0 0 0\nv 1 0 0\nv 1 1 0\nv 0 1 0\n";
+        let invalid_cardinal = format!("{vertices}cstype cardinal\ndeg 2\ncurv 0 1 1 2 3 4\nparm u 0 1\nend\n");
+        let invalid_bmatrix = format!("{vertices}cstype bmatrix\ndeg 3\nstep 1\nbmat u 1 0 0 0 1 0 0 0 1\ncurv 0 1 1 2 3 4\nparm u 0 1\nend\n");
+        for (source, error, line) in [(invalid_cardinal, FreeFormValidationExceptions::CardinalDegreeNotEqualToThree, 7), (invalid_bmatrix, FreeFormValidationExceptions::InvalidMatrixSize, 9)] {
+            let strict = ObjInterpreter::new(ObjParser::from_str(&source)).interpret().unwrap_err();
+            assert_eq!((strict.error.clone(), strict.span.start_line), (InterpreterError::FreeFormValidation(error), line));
+            assert_eq!(ObjParser::from_str(&source).visit(&mut Warnings(vec![])).unwrap_err(), strict);
+
+            // Lenient parsing keeps the element, with a warning
+            let model = ObjInterpreter::with_options(ObjParser::from_str(&source), ParseOptions::lenient()).interpret().unwrap();
+            assert_eq!(model.elements.len(), 1);
+            assert_eq!(model.warnings.iter().map(|warning| (warning.error, warning.span.start_line)).collect::<Vec<(Deviation, usize)>>(), vec![(Deviation::InvalidDefinition, line)]);
+
+            let mut warnings = Warnings(vec![]);
+            ObjParser::from_str(&source).visit_with_options(&mut warnings, ParseOptions::lenient()).unwrap();
+            assert_eq!(warnings.0, model.warnings);
+        }
     }
 
     #[test]
@@ -203,5 +270,1066 @@ mod tests {
         let bspline_parser_test = FreeFormDefinition::parse(&mut parser).unwrap();
         assert_eq!(bspline_test, bspline_parser_test);
     }
-    
-}
\ No newline at end of file
+
+    #[test]
+    fn test_interpreter() {
+        let model = ObjInterpreter::load("test_objs/interpreter_test.obj").unwrap();
+
+        assert_eq!(*model.vertex_buffer.get_vertex(Index::new(7).unwrap()).unwrap(), Vertex { x: 1.0, y: 1.0, z: 1.0, w: 1.0 });
+        assert_eq!(model.elements_of_type(ElementType::Face).count(), 6);
+        assert_eq!(model.elements_of_type(ElementType::Curve).count(), 1);
+
+        // Elements read under the same state share it
+        let faces: Vec<&Element> = model.elements_of_type(ElementType::Face).collect();
+        assert!(std::rc::Rc::ptr_eq(&faces[0].grouping, &faces[1].grouping));
+        assert!(!std::rc::Rc::ptr_eq(&faces[1].grouping, &faces[2].grouping));
+        assert_eq!(faces[2].grouping.groups, vec!["sides"]);
+        assert_eq!(faces[2].render_state.material.as_deref(), Some("blue"));
+
+        // Free-form elements own their body statements, and the definition active when they were read
+        if let Geometry::Curve(curve) = &model.elements_of_type(ElementType::Curve).next().unwrap().geometry {
+            assert_eq!(curve.body.parameters_u, vec![0.0, 1.0]);
+            assert_eq!(curve.definition.form_type, FreeFormType::Bezier);
+        } else {
+            panic!("Expected a curve");
+        }
+
+        // Body statements are only valid inside free-form elements
+        let mut interpreter = ObjInterpreter::new(ObjParser::new("test_objs/interpreter_test.obj").unwrap());
+        assert_eq!(interpreter.interpret_line(ObjLine::from("parm u 0.0 1.0")).unwrap_err().error, InterpreterError::BodyStatementOutsideElement);
+        assert_eq!(interpreter.interpret_line(ObjLine::from("bezier")).unwrap_err().error, InterpreterError::UnexpectedKeyword);
+        assert_eq!(interpreter.interpret_line(ObjLine::from("vc 1 0 0")).unwrap_err().error, InterpreterError::UnknownKeyword);
+    }
+
+    #[test]
+    fn test_polygonal_elements() {
+        let parameters = |line: &str| ObjLine::from(line).parameters;
+        let index = |value: isize| Index::new(value).unwrap();
+
+        // All four reference forms
+        let face = Face::from(&parameters("f 1/2/3 4/5/6 7/8/9")).unwrap();
+        assert_eq!(face.references[1], IndexTriplet::new(index(4), Some(index(5)), Some(index(6))));
+        assert_eq!(Face::from(&parameters("f 1//3 4//6 -1//9")).unwrap().format(), Some(TripletFormat::VertexNormal));
+        assert_eq!(Face::from(&parameters("f 1/2 3/4 5/6")).unwrap().format(), Some(TripletFormat::VertexTexture));
+        assert_eq!(Face::from(&parameters("f 1 2 3")).unwrap().format(), Some(TripletFormat::Vertex));
+
+        // Forms can't be mixed inside one element
+        assert_eq!(Face::from(&parameters("f 1/1 2/2/2 3/3")), Err(PolygonalElementError::MixedReferenceFormats));
+        assert_eq!(Face::from(&parameters("f 1 2//2 3")), Err(PolygonalElementError::MixedReferenceFormats));
+
+        // Element specific restrictions
+        assert_eq!(Line::from(&parameters("l 1/1 2/2 3/3")).unwrap().references.len(), 3);
+        assert_eq!(Line::from(&parameters("l 1//1 2//2")), Err(PolygonalElementError::InvalidReferenceFormat));
+        assert_eq!(Point::from(&parameters("p 1/1")), Err(PolygonalElementError::InvalidReferenceFormat));
+        assert_eq!(Face::from(&parameters("f 1 2")), Err(PolygonalElementError::NotEnoughReferences));
+
+        // Malformed references
+        assert_eq!(Face::from(&parameters("f 1/2/3/4 1 1")), Err(PolygonalElementError::InvalidReference(IndexTripletError::InvalidFormat)));
+        assert_eq!(Face::from(&parameters("f 0 1 2")), Err(PolygonalElementError::InvalidReference(IndexTripletError::InvalidIndex(IndexError::IndexIsZero))));
+        assert_eq!(Face::from(&parameters("f 1/ 2/ 3/")), Err(PolygonalElementError::InvalidReference(IndexTripletError::ConversionError)));
+    }
+
+    #[test]
+    fn test_relative_index_resolution() {
+        let index = |value: isize| Index::new(value).unwrap();
+
+        // Resolution is zero based, and checks the buffer bounds
+        assert_eq!(index(-1).resolve(4), Ok(3));
+        assert_eq!(index(2).resolve(4), Ok(1));
+        assert_eq!(index(-5).resolve(4), Err(IndexError::OutOfBounds));
+        assert_eq!(index(5).resolve(4), Err(IndexError::OutOfBounds));
+        assert_eq!(index(-1).resolve(0), Err(IndexError::OutOfBounds));
+
+        // Relative indices use the size of the buffer when the element was read
+        let model = ObjInterpreter::load("test_objs/relative_index_test.obj").unwrap();
+        let faces: Vec<&Element> = model.elements_of_type(ElementType::Face).collect();
+        if let (Geometry::Face(first), Geometry::Face(second)) = (&faces[0].geometry, &faces[1].geometry) {
+            assert_eq!(first.references[0], IndexTriplet::new(index(1), None, Some(index(1))));
+            assert_eq!(first.references[2].vertex, index(3));
+            assert_eq!(second.references.iter().map(|reference| reference.vertex).collect::<Vec<Index>>(), vec![index(2), index(3), index(4)]);
+        } else {
+            panic!("Expected faces");
+        }
+
+        // Out of range references are errors
+        let mut interpreter = ObjInterpreter::new(ObjParser::new("test_objs/relative_index_test.obj").unwrap());
+        assert_eq!(interpreter.interpret_line(ObjLine::from("f -1 -2 -3")).unwrap_err().error, InterpreterError::Index(IndexError::OutOfBounds));
+        interpreter.interpret_line(ObjLine::from("v 0.0 0.0 0.0")).unwrap();
+        assert_eq!(interpreter.interpret_line(ObjLine::from("f 1 2 -2")).unwrap_err().error, InterpreterError::Index(IndexError::OutOfBounds));
+        assert_eq!(model.vertex_buffer.get_vertex(index(-5)), Err(VertexBufferError::BoundsException));
+    }
+
+    #[test]
+    fn test_parser_sources() {
+        // In memory strings, with line continuations
+        let mut parser = ObjParser::from_str("# cube\nv 1.0 \\\n  2.0 3.0\nvn 0 0 1\n");
+        assert_eq!(parser.get_line().unwrap().comment, Some(" cube".to_owned()));
+
+        // Peeking doesn't progress the stream
+        assert_eq!(parser.get_next_keyword(), Some(Keyword::Vertex));
+        let vertex = parser.peek_line().unwrap();
+        assert_eq!(vertex, parser.get_line().unwrap());
+        assert_eq!(vertex.parameters, vec!["1.0", "2.0", "3.0"]);
+        assert_eq!(parser.get_next_keyword(), Some(Keyword::VertexNormal));
+        assert_eq!(parser.count(), 1);
+
+        // Any reader, seekable or not
+        let stream = "v 0 0 0\n".as_bytes().chain("v 1 0 0\nv 0 1 0\n".as_bytes()).chain("f 1 2 3\n".as_bytes());
+        let model = ObjInterpreter::new(ObjParser::from_reader(stream)).interpret().unwrap();
+        assert_eq!(model.elements_of_type(ElementType::Face).count(), 1);
+
+        // Embedded assets
+        let mut parser = ObjParser::from_str(include_str!("../test_objs/bspline_definition_test.obj"));
+        assert_eq!(FreeFormDefinition::parse(&mut parser).unwrap().degree, Degree::Surface(2, 2));
+    }
+
+    #[test]
+    fn test_spans() {
+        let source = "# header\n\n  v 1.0 2.0 3.0\nf 1 2 \\\n  3 4\nbezier\n";
+        let mut parser = ObjParser::from_str(source);
+
+        assert_eq!(parser.get_line().unwrap().span, Span::new(1, 1, 1, 9));
+        assert_eq!(parser.get_line().unwrap().span, Span::new(2, 2, 1, 1));
+        assert_eq!(parser.get_line().unwrap().span, Span::new(3, 3, 3, 16));
+
+        // Continuations keep the full line range
+        let face = parser.get_line().unwrap();
+        assert_eq!(face.span, Span::new(4, 5, 1, 6));
+        assert!(face.span.is_multiline());
+        assert_eq!(face.parameters, vec!["1", "2", "3", "4"]);
+
+        // Errors carry the span of the statement that caused them
+        let error = ObjInterpreter::new(ObjParser::from_str(source)).interpret().unwrap_err();
+        assert_eq!(error, Spanned::new(InterpreterError::Index(IndexError::OutOfBounds), Span::new(4, 5, 1, 6)));
+
+        let error = FreeFormDefinition::parse(&mut ObjParser::from_str("cstype bezier\ndeg 3 x\n")).unwrap_err();
+        assert_eq!(error, Spanned::new(FreeFormDefinitionError::InvalidParameters, Span::new(2, 2, 1, 8)));
+    }
+
+    #[test]
+    fn test_obj_error() {
+        use std::error::Error;
+
+        // I/O errors are kept
+        assert!(matches!(ObjInterpreter::load("test_objs/missing.obj"), Err(ObjError::IO(_))));
+
+        // "?" converts every module error, keeping the span
+        fn load(source: &str) -> Result<ObjModel, ObjError> {
+            Ok(ObjInterpreter::new(ObjParser::from_str(source)).interpret()?)
+        }
+        let error = load("v 0 0 0\nf 1 1 1/x\n").unwrap_err();
+        assert_eq!(error.span(), Some(Span::new(2, 2, 1, 10)));
+        assert!(matches!(error.inner(), ObjError::Interpreter(InterpreterError::PolygonalElement(_))));
+        assert_eq!(error.to_string(), "line 2, column 1: invalid element");
+
+        // Sources chain down to the original cause
+        let mut messages = vec![];
+        let mut source = error.source();
+        while let Some(error) = source {
+            messages.push(error.to_string());
+            source = error.source();
+        }
+        assert_eq!(messages, vec!["invalid vertex reference", "reference component isn't an integer"]);
+
+        let error: ObjError = UVPairError::InvalidBufferSize.into();
+        assert_eq!(error.to_string(), "expected one value for curves, or two for surfaces");
+    }
+
+    #[test]
+    fn test_writer_round_trip() {
+        let write = |model: &ObjModel| {
+            let mut writer = ObjWriter::new(Vec::<u8>::new());
+            writer.write_model(model).unwrap();
+            String::from_utf8(writer.into_inner()).unwrap()
+        };
+
+        for path in ["test_objs/round_trip_test.obj", "test_objs/interpreter_test.obj", "test_objs/freeform_elements_test.obj", "test_objs/body_statements_test.obj", "test_objs/connection_test.obj", "test_objs/render_state_test.obj"] {
+            let model = ObjInterpreter::load(path).unwrap();
+            let written = write(&model);
+            let mut reparsed = ObjInterpreter::new(ObjParser::from_str(&written)).interpret().unwrap();
+            reparsed.load_materials("test_objs");
+            assert_eq!(model, reparsed);
+
+            // Writing is stable
+            assert_eq!(written, write(&reparsed));
+        }
+
+        // State is only written when it changes, and optional components only when they aren't the default
+        let model = ObjInterpreter::load("test_objs/round_trip_test.obj").unwrap();
+        let written = write(&model);
+        assert_eq!(written.matches("cstype").count(), 2);
+        assert_eq!(written.matches("usemtl").count(), 1);
+        assert!(written.contains("v 0 1 0 0.5\n"));
+        assert!(written.contains("vt 0.25 0.5\n"));
+        assert!(written.contains("f 1/1/1 2/2/1 3/2/1\n"));
+        assert!(written.contains("cstype rat bezier\n"));
+
+        // Material libraries are kept, even without an element after them
+        let model = ObjInterpreter::new(ObjParser::from_str("mtllib a.mtl\nv 0 0 0\np 1\nmtllib b.mtl\n")).interpret().unwrap();
+        let written = write(&model);
+        assert!(written.starts_with("mtllib a.mtl b.mtl\n"));
+        assert_eq!(ObjInterpreter::new(ObjParser::from_str(&written)).interpret().unwrap().material_libraries, vec!["a.mtl", "b.mtl"]);
+    }
+
+    #[test]
+    fn test_material_library() {
+        let library = MaterialLibrary::load("test_objs/cube.mtl").unwrap();
+        assert_eq!(library.len(), 2);
+
+        let red = library.get("red").unwrap();
+        assert_eq!(red.ambient, Some(Color::RGB(0.1, 0.1, 0.1)));
+        assert_eq!(red.diffuse, Some(Color::RGB(1.0, 0.0, 0.0)));
+        assert_eq!(red.specular, Some(Color::Spectral("steel.rfl".to_owned(), 0.5)));
+        assert_eq!(red.transmission_filter, Some(Color::XYZ(1.0, 1.0, 1.0)));
+        assert_eq!(red.illumination_model, Some(2));
+        assert_eq!(red.dissolve, Some(Dissolve { factor: 0.75, halo: true }));
+        assert_eq!((red.specular_exponent, red.sharpness, red.optical_density), (Some(64.0), Some(100.0), Some(1.45)));
+        assert_eq!(red.anti_aliasing, Some(true));
+
+        // Texture map options come before the file name
+        let diffuse_map = red.diffuse_map.as_ref().unwrap();
+        assert_eq!(diffuse_map.file, "textures/red brick.png");
+        assert!(!diffuse_map.options.blend_u && diffuse_map.options.blend_v);
+        assert_eq!(diffuse_map.options.offset, (0.5, 0.5, 0.0));
+        assert_eq!(diffuse_map.options.scale, (2.0, 1.0, 1.0));
+        assert_eq!(diffuse_map.options.range, (0.1, 0.9));
+
+        let bump = red.bump.as_ref().unwrap();
+        assert_eq!((bump.options.bump_multiplier, bump.options.channel), (Some(0.25), Some(MapChannel::Luminance)));
+
+        let blue = library.get("blue").unwrap();
+        assert_eq!(blue.reflection_maps.iter().map(|map| map.options.reflection_type.unwrap()).collect::<Vec<_>>(), vec![ReflectionType::CubeTop, ReflectionType::CubeBottom]);
+
+        // Invalid statements
+        let parse = |source: &str| MaterialLibrary::parse(&mut ObjParser::from_str(source)).unwrap_err();
+        assert_eq!(parse("Kd 1 0 0\n"), Spanned::new(MaterialError::MissingMaterial, Span::new(1, 1, 1, 9)));
+        assert_eq!(parse("newmtl a\nrefl sky.png\n").error, MaterialError::InvalidOption);
+        assert_eq!(parse("newmtl a\nmap_Kd -bm 2 a.png\n").error, MaterialError::InvalidOption);
+        assert_eq!(parse("newmtl a\nmap_Kd -clamp on\n").error, MaterialError::MissingFileName);
+        assert_eq!(parse("newmtl a\nillum 11\n").error, MaterialError::InvalidParameters);
+
+        // Statements added by exporters are skipped when unknown keywords are allowed
+        assert_eq!(parse("newmtl a\nKe 0 0 0\n"), Spanned::new(MaterialError::UnknownKeyword, Span::new(2, 2, 1, 9)));
+        let library = MaterialLibrary::parse_with_options(&mut ObjParser::from_str("newmtl a\nKe 0 0 0\nKd 1 0 0\n"), ParseOptions::lenient()).unwrap();
+        assert_eq!(library.get("a").unwrap().diffuse, Some(Color::RGB(1.0, 0.0, 0.0)));
+        assert_eq!(library.warnings, vec![Spanned::new(Deviation::UnknownKeyword, Span::new(2, 2, 1, 9))]);
+
+        // Even before the first "newmtl"
+        let library = MaterialLibrary::parse_with_options(&mut ObjParser::from_str("Pr 0.5\nnewmtl a\nKd 1 0 0\n"), ParseOptions::lenient()).unwrap();
+        assert_eq!((library.len(), library.warnings.clone()), (1, vec![Spanned::new(Deviation::UnknownKeyword, Span::new(1, 1, 1, 7))]));
+        assert_eq!(parse("Pr 0.5\nnewmtl a\n").error, MaterialError::UnknownKeyword);
+        assert_eq!(parse("Kd 1 0 0\n").error, MaterialError::MissingMaterial);
+
+        // Materials are linked to the elements that use them
+        let model = ObjInterpreter::load("test_objs/interpreter_test.obj").unwrap();
+        let faces: Vec<&Element> = model.elements_of_type(ElementType::Face).collect();
+        assert_eq!(model.material(faces[0]).unwrap().name, "red");
+        assert_eq!(model.material(faces[5]), Some(blue));
+        assert_eq!(model.elements_with_material("blue").filter(|element| element.element_type() == ElementType::Face).count(), 4);
+        assert!(model.unresolved_libraries.is_empty());
+
+        // Libraries that can't be loaded don't stop the others, or the geometry
+        let mut model = ObjInterpreter::new(ObjParser::from_str("mtllib missing.mtl cube.mtl\nv 0 0 0\np 1\n")).interpret().unwrap();
+        model.load_materials("test_objs");
+        assert_eq!((model.elements.len(), model.materials.len()), (1, 2));
+        assert_eq!(model.unresolved_libraries, vec![UnresolvedLibrary { library: "missing.mtl".to_owned(), error: MaterialError::IO(std::io::ErrorKind::NotFound.into()), span: None }]);
+
+        // Exporter statements follow the parse options
+        let mut model = ObjInterpreter::new(ObjParser::from_str("mtllib exporter_test.mtl\n")).interpret().unwrap();
+        model.load_materials("test_objs");
+        assert_eq!(model.unresolved_libraries[0].error, MaterialError::UnknownKeyword);
+        assert_eq!(model.unresolved_libraries[0].span, Some(Span::new(4, 4, 1, 11)));
+        model.unresolved_libraries.clear();
+        model.load_materials_with_options("test_objs", ParseOptions::lenient());
+        assert!(model.unresolved_libraries.is_empty() && model.materials.get("glow").is_some());
+        assert_eq!(model.materials.warnings.len(), 2);
+    }
+
+    #[test]
+    fn test_free_form_elements() {
+        let model = ObjInterpreter::load("test_objs/freeform_elements_test.obj").unwrap();
+        let index = |value: isize| Index::new(value).unwrap();
+        let geometry: Vec<&Geometry> = model.elements.iter().map(|element| &element.geometry).collect();
+
+        if let [Geometry::Surface(surface), Geometry::Curve2D(trim), Geometry::Curve(curve)] = geometry.as_slice() {
+            assert_eq!((surface.s_range, surface.t_range), ((0.0, 1.0), (0.0, 1.0)));
+            assert_eq!(surface.references[4], IndexTriplet::new(index(5), Some(index(1)), None));
+            assert_eq!(surface.definition.degree, Degree::Surface(2, 2));
+
+            // Relative references resolve against the parameter space vertices for 2D curves
+            assert_eq!(trim.vertices, vec![index(1), index(2), index(3)]);
+            assert_eq!(trim.definition.degree, Degree::Curve(2));
+            assert_eq!((curve.start, curve.end), (0.0, 2.0));
+            assert_eq!(curve.vertices, vec![index(1), index(2), index(3)]);
+
+            // Elements read under the same definition share it
+            assert!(std::rc::Rc::ptr_eq(&trim.definition, &curve.definition));
+            assert_eq!(curve.definition.form_type, FreeFormType::Bezier);
+        } else {
+            panic!("Expected a surface, a 2D curve and a curve");
+        }
+
+        // Curves need a curve degree, surfaces a surface degree
+        let parse = |source: &str| ObjInterpreter::new(ObjParser::from_str(source)).interpret().unwrap_err().error;
+        assert_eq!(parse("v 0 0 0\ncstype bezier\ndeg 1 1\ncurv 0 1 1 1\n"), InterpreterError::FreeFormElement(FreeFormElementError::CurveSurfaceMismatch));
+        assert_eq!(parse("v 0 0 0\ncstype bezier\ndeg 1\ncurv 0 1 1\n"), InterpreterError::FreeFormElement(FreeFormElementError::NotEnoughReferences));
+        assert_eq!(parse("v 0 0 0\ncstype bezier\ndeg 1\ncurv 0 1 1/1 1/1\n"), InterpreterError::FreeFormElement(FreeFormElementError::InvalidReferenceFormat));
+        assert_eq!(parse("v 0 0 0\ncstype bezier\ndeg 1 1\nsurf 0 1 0 1 1 1//1\n"), InterpreterError::FreeFormElement(FreeFormElementError::MixedReferenceFormats));
+        assert_eq!(parse("v 0 0 0\ncstype bezier\ndeg 1\ncurv 0 1 1 2\n"), InterpreterError::Index(IndexError::OutOfBounds));
+        assert_eq!(parse("deg 1\ncurv 0 1 1 2\n"), InterpreterError::FreeFormDefinition(FreeFormDefinitionError::MalformedDefinition));
+    }
+
+    #[test]
+    fn test_body_statements() {
+        let model = ObjInterpreter::load("test_objs/body_statements_test.obj").unwrap();
+        let index = |value: isize| Index::new(value).unwrap();
+        let segment = |start: f32, end: f32, curve: isize| CurveReference { start, end, curve: index(curve) };
+        let geometry: Vec<&Geometry> = model.elements.iter().map(|element| &element.geometry).collect();
+
+        if let [Geometry::Curve2D(outer), Geometry::Curve2D(_), Geometry::Surface(surface)] = geometry.as_slice() {
+            assert_eq!(outer.body.parameters_u, vec![0.0, 1.0, 2.0, 3.0]);
+            assert!(outer.body.parameters_v.is_empty());
+
+            // 2D curve references resolve against the curves read so far
+            let body = &surface.body;
+            assert_eq!((body.parameters_u.clone(), body.parameters_v.clone()), (vec![0.0, 1.0], vec![0.0, 1.0]));
+            assert_eq!(body.trim_loops, vec![vec![segment(0.0, 3.0, 1)]]);
+            assert_eq!(body.holes, vec![vec![segment(0.0, 1.0, 2), segment(1.0, 3.0, 2)]]);
+            assert_eq!(body.special_curves, vec![vec![segment(0.0, 1.0, 2)]]);
+            assert_eq!(body.special_points, vec![index(4), index(6)]);
+        } else {
+            panic!("Expected two 2D curves and a surface");
+        }
+
+        let parse = |source: &str| ObjInterpreter::new(ObjParser::from_str(source)).interpret().unwrap_err();
+        let curve = "vp 0 0\nvp 1 1\ncstype bezier\ndeg 1\ncurv2 1 2\n";
+        let surface = "v 0 0 0\ncstype bezier\ndeg 1 1\nsurf 0 1 0 1 1 1 1 1\n";
+
+        // Surface only statements
+        assert_eq!(parse(&format!("{curve}parm v 0 1\nend\n")).error, InterpreterError::BodyStatement(BodyStatementError::NotValidForElement));
+        assert_eq!(parse(&format!("{curve}trim 0 1 1\nend\n")).error, InterpreterError::BodyStatement(BodyStatementError::NotValidForElement));
+        // Malformed statements
+        assert_eq!(parse(&format!("{curve}parm w 0 1\nend\n")).error, InterpreterError::BodyStatement(BodyStatementError::InvalidAxis));
+        assert_eq!(parse(&format!("{curve}parm u\nend\n")).error, InterpreterError::BodyStatement(BodyStatementError::InvalidParameters));
+        assert_eq!(parse(&format!("{surface}trim 0 1\nend\n")).error, InterpreterError::BodyStatement(BodyStatementError::InvalidParameters));
+        assert_eq!(parse(&format!("{surface}trim 0 1 1\nend\n")).error, InterpreterError::BodyStatement(BodyStatementError::Index(IndexError::OutOfBounds)));
+        assert_eq!(parse(&format!("{surface}sp 0\nend\n")).error, InterpreterError::BodyStatement(BodyStatementError::InvalidReference));
+
+        // Missing "end" is reported where the element starts
+        assert_eq!(parse(curve), Spanned::new(InterpreterError::MissingEnd, Span::new(5, 5, 1, 10)));
+        assert_eq!(parse(&format!("{curve}curv2 1 2\nend\n")), Spanned::new(InterpreterError::MissingEnd, Span::new(5, 5, 1, 10)));
+    }
+
+    #[test]
+    fn test_connections() {
+        let model = ObjInterpreter::load("test_objs/connection_test.obj").unwrap();
+        let index = |value: isize| Index::new(value).unwrap();
+
+        // Relative references resolve against the surfaces and 2D curves read so far
+        assert_eq!(model.connections, vec![Connection {
+            first:  SurfaceCurve { surface: index(1), start: 0.0, end: 1.0, curve: index(1) },
+            second: SurfaceCurve { surface: index(2), start: 0.0, end: 1.0, curve: index(2) }
+        }]);
+        // Surfaces and 2D curves are numbered on their own
+        let surface = |element: usize| match &model.elements[element].geometry { Geometry::Surface(surface) => Some(surface), _ => None };
+        assert_eq!(model.surface(index(1)), surface(2));
+        assert_eq!(model.surface(index(-1)), surface(3));
+        assert!(model.surface(index(3)).is_none() && model.curve_2d(index(-3)).is_none());
+        assert!(matches!(model.elements[1].geometry, Geometry::Curve2D(ref curve) if model.curve_2d(index(2)) == Some(curve)));
+
+        assert!(model.validate_connections().is_ok());
+        assert_eq!(model.connections[0].second.evaluate(&model, 0.5).unwrap(), [1.0, 0.5, 0.0]);
+        assert_eq!(model.largest_connection_gap(8).unwrap(), 0.0);
+
+        // Moving one side of the seam opens a crack
+        let mut cracked = model.clone();
+        cracked.vertex_buffer = VertexBuffer::new();
+        for (i, vertex) in model.vertex_buffer.vertices().iter().enumerate() {
+            let mut vertex = (**vertex).clone();
+            if i == 4 || i == 6 {
+                vertex.z = 0.5;
+            }
+            cracked.vertex_buffer.add_vertex(vertex);
+        }
+        for vertex in model.vertex_buffer.parameter_space_vertices() {
+            cracked.vertex_buffer.add_parameter_space_vertex((**vertex).clone());
+        }
+        assert_eq!(cracked.largest_connection_gap(8).unwrap(), 0.5);
+
+        let mut source = String::new();
+        std::fs::File::open("test_objs/connection_test.obj").unwrap().read_to_string(&mut source).unwrap();
+        let source = source.replace("con -2 0.0 1.0 1 -1 0.0 1.0 2\n", "");
+        let parse = |connection: &str| ObjInterpreter::new(ObjParser::from_str(&format!("{source}{connection}\n"))).interpret().unwrap_err().error;
+
+        assert_eq!(parse("con 1 0 1 1 2 0 1"), InterpreterError::Connection(ConnectionError::InvalidParameters));
+        assert_eq!(parse("con 1 0 1 0 2 0 1 2"), InterpreterError::Connection(ConnectionError::InvalidReference));
+        assert_eq!(parse("con -3 0 1 1 2 0 1 2"), InterpreterError::Connection(ConnectionError::Index(IndexError::OutOfBounds)));
+        // Absolute references are checked after the whole file is read
+        assert_eq!(parse("con 3 0 1 1 2 0 1 2"), InterpreterError::Connection(ConnectionError::MissingSurface));
+        assert_eq!(parse("con 1 0 1 3 2 0 1 2"), InterpreterError::Connection(ConnectionError::MissingCurve));
+        assert_eq!(parse("con 1 0 2 1 2 0 1 2"), InterpreterError::Connection(ConnectionError::InvalidRange));
+        assert_eq!(parse("con 1 1 1 1 2 0 1 2"), InterpreterError::Connection(ConnectionError::InvalidRange));
+
+        // B-splines are only defined between knot [degree] and knot [control points]
+        let clamped = "cstype bspline\ndeg 2\ncurv2 1 2 3 4\nparm u 0 0 0 0.5 1 1 1\nend\n";
+        let uniform = "cstype bspline\ndeg 2\ncurv2 1 2 3 4\nparm u 0 1 2 3 4 5 6\nend\n";
+        let interpret = |curve: &str, connection: &str| ObjInterpreter::new(ObjParser::from_str(&format!("{source}{curve}{connection}\n"))).interpret().map(|_| ()).map_err(|error| error.error);
+        assert_eq!(interpret(clamped, "con 1 0 1 3 2 0 1 2"), Ok(()));
+        assert_eq!(interpret(clamped, "con 1 0 1.5 3 2 0 1 2"), Err(InterpreterError::Connection(ConnectionError::InvalidRange)));
+        assert_eq!(interpret(uniform, "con 1 2 4 3 2 0 1 2"), Ok(()));
+        assert_eq!(interpret(uniform, "con 1 0 1 3 2 0 1 2"), Err(InterpreterError::Connection(ConnectionError::InvalidRange)));
+        assert_eq!(interpret(uniform, "con 1 5 6 3 2 0 1 2"), Err(InterpreterError::Connection(ConnectionError::InvalidRange)));
+    }
+
+    #[test]
+    fn test_grouping() {
+        let model = ObjInterpreter::load("test_objs/round_trip_test.obj").unwrap();
+
+        assert_eq!(model.groups(), vec!["points", "markers", "edges", "faces", "curves"]);
+        assert_eq!(model.objects(), vec!["scene"]);
+        assert_eq!(model.elements_in_group("markers").count(), 1);
+        assert_eq!(model.elements_of_type_in_group(ElementType::Face, "faces").count(), 2);
+        assert_eq!(model.elements_of_type_in_group(ElementType::Line, "faces").count(), 0);
+        assert_eq!(model.elements_in_object("scene").count(), model.elements.len());
+
+        // Grouping statements are state-setting, elements keep the state they were read under
+        let curve = model.elements_of_type(ElementType::Curve).next().unwrap();
+        assert_eq!(curve.grouping.smoothing_group, Some(1));
+        assert_eq!(curve.grouping.merging_group, Some(MergingGroup { group: 1, resolution: 0.5 }));
+        assert_eq!(model.elements[0].grouping.merging_group, None);
+
+        let apply = |keyword: &str, parameters: &str| {
+            let mut grouping = Grouping::new();
+            grouping.apply(keyword, &ObjLine::from(format!("{keyword} {parameters}").as_str()).parameters).map(|_| grouping)
+        };
+        assert_eq!(apply("g", "").unwrap().groups, vec!["default"]);
+        assert_eq!(apply("s", "off").unwrap(), apply("s", "0").unwrap());
+        assert_eq!(apply("mg", "off").unwrap().merging_group, None);
+        assert_eq!(apply("mg", "2"), Err(GroupingError::InvalidResolution));
+        assert_eq!(apply("mg", "2 0"), Err(GroupingError::InvalidResolution));
+        assert_eq!(apply("s", "-1"), Err(GroupingError::InvalidGroupNumber));
+        assert_eq!(apply("o", ""), Err(GroupingError::InvalidParameters));
+    }
+
+    #[test]
+    fn test_render_state() {
+        let model = ObjInterpreter::load("test_objs/render_state_test.obj").unwrap();
+        let states: Vec<&RenderState> = model.elements.iter().map(|element| element.render_state.as_ref()).collect();
+
+        assert_eq!(states[0].map_libraries, vec!["textures.mpc", "patterns.mpc"]);
+        assert_eq!(states[0].texture_map.as_deref(), Some("checker"));
+        assert!(states[0].bevel && states[0].color_interpolation && !states[0].dissolve_interpolation);
+        assert_eq!(states[0].level_of_detail, 50);
+        assert_eq!((states[0].shadow_object.as_deref(), states[0].trace_object.as_deref()), (Some("shadow.obj"), Some("trace.obj")));
+        assert_eq!(states[0].curve_approximation, None);
+
+        // State carries over to the elements that follow
+        assert_eq!(states[1].texture_map, None);
+        assert!(states[1].bevel && states[1].dissolve_interpolation);
+        assert_eq!(states[1].curve_approximation, Some(CurveApproximation::CurvatureDependent { max_distance: 0.1, max_angle: 5.0 }));
+        assert_eq!(states[1].surface_approximation, Some(SurfaceApproximation::ConstantParametric { u_resolution: 4.0, v_resolution: 8.0 }));
+        assert_eq!(states[2].curve_approximation, Some(CurveApproximation::ConstantParametric { resolution: 10.0 }));
+        assert_eq!(states[2].surface_approximation, states[1].surface_approximation);
+
+        let apply = |keyword: &str, parameters: &str| {
+            let mut state = RenderState::new();
+            state.apply(keyword, &ObjLine::from(format!("{keyword} {parameters}").as_str()).parameters).map(|_| state)
+        };
+        assert_eq!(apply("stech", "cparmb 2").unwrap().surface_approximation, Some(SurfaceApproximation::ConstantParametricSingle { resolution: 2.0 }));
+        assert_eq!(apply("stech", "cspace 0.5").unwrap().surface_approximation, Some(SurfaceApproximation::ConstantSpatial { max_length: 0.5 }));
+        assert_eq!(apply("bevel", "yes"), Err(RenderStateError::InvalidSwitch));
+        assert_eq!(apply("lod", "101"), Err(RenderStateError::InvalidLevelOfDetail));
+        assert_eq!(apply("ctech", "cparma 1 1"), Err(RenderStateError::UnknownTechnique));
+        assert_eq!(apply("ctech", "curv 0.1"), Err(RenderStateError::InvalidParameters));
+        assert_eq!(apply("usemtl", ""), Err(RenderStateError::InvalidParameters));
+    }
+
+    #[test]
+    fn test_keywords() {
+        for keyword in ["v", "vt", "vn", "vp", "cstype", "deg", "bmat", "step", "p", "l", "f", "curv", "curv2", "surf", "parm", "trim", "hole", "scrv", "sp", "end",
+                        "con", "g", "s", "mg", "o", "bevel", "c_interp", "d_interp", "lod", "maplib", "usemap", "usemtl", "mtllib", "shadow_obj", "trace_obj", "ctech", "stech"] {
+            let parsed: Keyword = keyword.parse().unwrap();
+            assert!(!parsed.is_unknown(), "{keyword} should be known");
+            assert_eq!(parsed.to_string(), keyword);
+        }
+
+        // Parameter keywords and statements from other formats aren't OBJ statements
+        assert_eq!(Keyword::from("bezier"), Keyword::Unknown("bezier".to_owned()));
+        assert_eq!(Keyword::from("newmtl").to_string(), "newmtl");
+        assert_eq!(Keyword::from("curv2"), Keyword::Curve2D);
+        assert_eq!(Keyword::Surface, "surf");
+
+        assert_eq!(ObjLine::from("f 1 2 3 # comment").keyword, Some(Keyword::Face));
+        assert_eq!(ObjLine::from("vc 1 0 0").keyword, Some(Keyword::Unknown("vc".to_owned())));
+        assert_eq!(ObjLine::from("# comment only").keyword, None);
+    }
+
+    #[test]
+    fn test_line_refs() {
+        let line = ObjLineRef::from("  f 1/1 2/2\t3/3   # triangle");
+        assert_eq!(line.keyword, Some("f"));
+        assert_eq!(line.parameters().collect::<Vec<&str>>(), vec!["1/1", "2/2", "3/3"]);
+        assert_eq!(line.comment, Some(" triangle"));
+        assert_eq!(line.keyword(), Some(Keyword::Face));
+        assert_eq!(line.to_line(), ObjLine::from("  f 1/1 2/2\t3/3   # triangle"));
+
+        assert_eq!(ObjLineRef::from("end").keyword, Some("end"));
+        assert_eq!(ObjLineRef::from("end").parameters().count(), 0);
+        assert_eq!(ObjLineRef::from("   ").keyword, None);
+        assert_eq!(ObjLineRef::from("#").comment, None);
+
+        // Continuations are joined in the parser's buffer, peeked lines are borrowed from it too
+        let mut parser = ObjParser::from_str("v 1 \\\n  2 3\n# comment\nvn 0 0 1\n");
+        let line = parser.get_line_ref().unwrap();
+        assert_eq!(line.parameters().collect::<Vec<&str>>(), vec!["1", "2", "3"]);
+        assert_eq!(line.span, Span::new(1, 2, 1, 6));
+
+        assert_eq!(parser.get_line_ref().unwrap().comment, Some(" comment"));
+        assert_eq!(parser.peek_line_ref().unwrap().keyword, Some("vn"));
+        assert_eq!(parser.get_next_keyword(), Some(Keyword::VertexNormal));
+        assert_eq!(parser.get_line_ref().unwrap().span, Span::new(4, 4, 1, 9));
+        assert!(parser.peek_line_ref().is_none());
+        assert!(parser.get_line_ref().is_none());
+    }
+
+    #[test]
+    fn test_numeric_fast_path() {
+        // Matches str::parse, whether or not the fast path handles it
+        for text in ["0", "-0", "1", "+2.5", "-0.125", "0.994987", "12345678", "16777217", "3.14159265358979", ".5", "5.", "1e10", "1.5E-3", "2e-11", "1e39",
+                     "0.000001", "-123.456789", "inf", "-inf", "NaN", "", "-", ".", "e5", "1e", "1.2.3", "1,5", "0x10", " 1"] {
+            let expected = text.parse::<f32>().ok();
+            let parsed = utility::parse_f32(text);
+            assert_eq!(parsed.map(f32::to_bits), expected.map(f32::to_bits), "{text:?}");
+        }
+
+        for text in ["0", "1", "-1", "+7", "9223372036854775807", "-9223372036854775808", "9223372036854775808", "", "-", "1.0", "1a", " 1"] {
+            assert_eq!(utility::parse_isize(text), text.parse::<isize>().ok(), "{text:?}");
+        }
+
+        assert_eq!(Vertex::parse("1 2 3"), Some(Vertex { x: 1.0, y: 2.0, z: 3.0, w: 1.0 }));
+        assert_eq!(Vertex::parse("1 2 3 4 5"), None);
+        assert_eq!(TextureCoordinate::parse("0.5"), Some(TextureCoordinate { u: 0.5, v: 0.0, w: 0.0 }));
+        assert_eq!(TextureCoordinate::parse("0.5 0.25 0 1"), Some(TextureCoordinate { u: 0.5, v: 0.25, w: 0.0 }));
+        assert_eq!(TextureCoordinate::parse("0.5 0.25 0 x"), None);
+        assert_eq!(VertexNormal::parse("0 0 1 0"), Some(VertexNormal { i: 0.0, j: 0.0, k: 1.0 }));
+        assert_eq!(VertexNormal::parse("0 0 1 x"), None);
+        assert_eq!(ParameterSpaceVertex::parse("0.5"), None);
+    }
+
+    #[test]
+    fn test_parallel_loading() {
+        let sequential = |source: &str| ObjInterpreter::new(ObjParser::from_str(source)).interpret();
+
+        // Chunks are only split between lines, never inside a continuation
+        let source = "v 1 \\\n 2 3\nv 4 5 6\n";
+        assert_eq!(parallel::chunk_boundary(source.as_bytes(), 0), 11);
+        assert_eq!(parallel::chunk_boundary(source.as_bytes(), 8), 11);
+        assert_eq!(parallel::chunk_boundary(source.as_bytes(), 11), 19);
+        assert_eq!(parallel::chunk_boundary(source.as_bytes(), 40), 19);
+
+        // Chunks read from a file end the same way
+        let mut reader = source.as_bytes();
+        assert_eq!(parallel::read_chunk(&mut reader, 0).unwrap(), b"v 1 \\\n 2 3\n");
+        assert_eq!(parallel::read_chunk(&mut reader, 2).unwrap(), b"v 4 5 6\n");
+        assert!(parallel::read_chunk(&mut reader, 2).unwrap().is_empty());
+
+        // Small chunks put relative indices, continuations and free-form blocks across chunk boundaries
+        let mut generated = String::from("mtllib cube.mtl\ng grid\nusemtl red\n");
+        for row in 0..8 {
+            generated += &format!("v 0 {row} 0\nv 1 \\\n  {row} 0\nvt 0 {row}\nvn 0 0 1\n");
+            if row > 0 {
+                generated += "f -4/-2/-1 -3/-2/-1 \\\n  -1/-1/-1 -2/-1/-1\n";
+            }
+        }
+        generated += "cstype bezier\ndeg 1\ncurv 0.0 1.0 1 -1\nparm u 0.0 1.0\nend\nl 1 2 3\n";
+
+        let fixtures = ["interpreter_test.obj", "relative_index_test.obj", "freeform_elements_test.obj", "body_statements_test.obj", "connection_test.obj", "render_state_test.obj"];
+        let mut sources: Vec<String> = fixtures.iter().map(|name| std::fs::read_to_string(format!("test_objs/{name}")).unwrap()).collect();
+        sources.push(generated);
+
+        for source in &sources {
+            let expected = sequential(source).unwrap();
+            for chunk_size in [0, 16, 100, 1 << 20] {
+                assert_eq!(parallel::interpret_bytes(source.as_bytes(), 3, chunk_size, ParseOptions::default()).unwrap(), expected);
+                assert_eq!(parallel::interpret_reader(source.as_bytes(), 3, chunk_size, ParseOptions::default()).unwrap(), expected);
+            }
+            assert_eq!(interpret_parallel(source, 4, ParseOptions::default()).unwrap(), expected);
+        }
+
+        // Errors are reported at the same place, even in later chunks
+        for source in ["v 0 0 0\nv 1 1 1\n\\\n\nf 1 2 -3\n", "curv2 1 2\nv 0 0 0\nf 1 1 1\n", "v 0 0 0\np 1\nv a b c\n"] {
+            assert_eq!(parallel::interpret_bytes(source.as_bytes(), 2, 0, ParseOptions::default()).unwrap_err(), sequential(source).unwrap_err());
+        }
+
+        // Lines that aren't UTF-8 too, unless they're decoded as Latin-1
+        let source = &b"g caf\xe9\nv 0 0 0\nv 1 1 1\np 1\n"[..];
+        let parse = |options: ParseOptions| ObjInterpreter::with_options(ObjParser::from_buf_read(source), options).interpret();
+        let parse_source = |source: &[u8]| ObjInterpreter::new(ObjParser::from_buf_read(source)).interpret().unwrap_err();
+        assert_eq!(parallel::interpret_reader(source, 2, 0, ParseOptions::default()).unwrap_err(), parse(ParseOptions::default()).unwrap_err());
+        assert_eq!(parallel::interpret_bytes(source, 2, 0, ParseOptions::lenient()).unwrap(), parse(ParseOptions::lenient()).unwrap());
+
+        // Every line that isn't UTF-8 in a chunk is reported, in order
+        let source = &b"v 0 0 0\n\xff\nv 1 1 1\ng caf\xe9\np 1\n"[..];
+        let chunk = parallel::parse_chunk(source, ParseOptions::default());
+        let errors: Vec<Span> = chunk.statements.iter().filter(|(statement, _)| matches!(statement, parallel::ParsedStatement::Error(_))).map(|(_, span)| *span).collect();
+        assert_eq!((errors, chunk.lines), (vec![Span::line(2), Span::line(4)], 5));
+        assert_eq!(parallel::interpret_bytes(source, 2, 1 << 20, ParseOptions::default()).unwrap_err(), parse_source(source));
+
+        let model = ObjInterpreter::load_parallel("test_objs/interpreter_test.obj").unwrap();
+        assert_eq!(model, ObjInterpreter::load("test_objs/interpreter_test.obj").unwrap());
+
+        let lenient = ObjInterpreter::load_with_options("test_objs/lenient_test.obj", ParseOptions::lenient()).unwrap();
+        assert_eq!(ObjInterpreter::load_parallel_with_options("test_objs/lenient_test.obj", ParseOptions::lenient()).unwrap(), lenient);
+    }
+
+    #[cfg(feature = "memmap")]
+    #[test]
+    fn test_mapped_files() {
+        // Lines are read out of the mapping
+        let mut parser = ObjParser::map("test_objs/relative_index_test.obj").unwrap();
+        let mut reader = ObjParser::new("test_objs/relative_index_test.obj").unwrap();
+        while let Some(line) = reader.get_line() {
+            assert_eq!(parser.get_line(), Some(line));
+        }
+        assert_eq!(parser.get_line(), None);
+
+        let file = MappedFile::open("test_objs/interpreter_test.obj").unwrap();
+        assert_eq!(file.as_str().unwrap(), std::fs::read_to_string("test_objs/interpreter_test.obj").unwrap());
+
+        let expected = ObjInterpreter::load("test_objs/interpreter_test.obj").unwrap();
+        assert_eq!(ObjInterpreter::load_mapped("test_objs/interpreter_test.obj").unwrap(), expected);
+        assert_eq!(ObjInterpreter::load_parallel("test_objs/interpreter_test.obj").unwrap(), expected);
+        assert!(matches!(ObjInterpreter::load_mapped("test_objs/missing.obj"), Err(ObjError::IO(_))));
+
+        let lenient = ObjInterpreter::load_with_options("test_objs/lenient_test.obj", ParseOptions::lenient()).unwrap();
+        assert_eq!(ObjInterpreter::load_mapped_with_options("test_objs/lenient_test.obj", ParseOptions::lenient()).unwrap(), lenient);
+
+        // Mapped files are interpreted from lines borrowed straight out of the mapping, matching the parser line for line
+        use crate::parser::LineSource;
+        use crate::mapped::MappedLines;
+        for path in ["test_objs/interpreter_test.obj", "test_objs/bmatrix_curve_definition_test_01.obj", "test_objs/lenient_test.obj"] {
+            let file = MappedFile::open(path).unwrap();
+            let mapping = file.as_bytes().as_ptr_range();
+            let mut lines = MappedLines::new(file.as_bytes(), false);
+            let mut parser = ObjParser::new(path).unwrap();
+            while let Some(expected) = parser.get_line_ref() {
+                let (line, latin1) = lines.next_line().unwrap();
+                assert_eq!((line, latin1), (expected, false));
+                // Only lines joined with "\" are copied
+                if let (Some(keyword), false) = (line.keyword, line.span.end_line > line.span.start_line) {
+                    assert!(mapping.contains(&keyword.as_ptr()), "{path}: {line:?}");
+                }
+            }
+            assert!(lines.next_line().is_none());
+        }
+
+        // Lines that aren't UTF-8 are errors, or decoded as Latin-1, the same way
+        let source = b"v 0 0 0\ng caf\xE9\nv 1 \\\n  1 1\nv 2 2 2\n";
+        let mut lines = MappedLines::new(source, true);
+        let mut parser = ObjParser::from_buf_read(&source[..]);
+        parser.set_latin1_fallback(true);
+        while let Some(expected) = parser.next_line() {
+            assert_eq!(lines.next_line(), Some(expected));
+        }
+        assert!(lines.next_line().is_none());
+
+        for options in [ParseOptions::strict(), ParseOptions::lenient()] {
+            let expected = ObjInterpreter::with_options(ObjParser::from_buf_read(&source[..]), options).interpret();
+            assert_eq!(ObjInterpreter::with_options(ObjParser::from_str(""), options).interpret_lines(&mut MappedLines::new(source, options.latin1_text)), expected);
+        }
+    }
+
+    #[test]
+    fn test_visitor() {
+        // Keeps what the model would, to compare against the interpreter
+        #[derive(Default)]
+        struct Collector {
+            vertices:   Vec<Vertex>,
+            normals:    usize,
+            elements:   Vec<Geometry>,
+            open:       Option<Geometry>,
+            body:       Vec<Keyword>,
+            groups:     Vec<Vec<String>>,
+            materials:  Vec<String>,
+            statements: usize
+        }
+
+        impl ObjVisitor for Collector {
+            fn vertex(&mut self, vertex: Vertex) { self.vertices.push(vertex); }
+            fn normal(&mut self, _normal: VertexNormal) { self.normals += 1; }
+            fn point(&mut self, point: &Point) { self.elements.push(Geometry::Point(point.clone())); }
+            fn line(&mut self, line: &Line) { self.elements.push(Geometry::Line(line.clone())); }
+            fn face(&mut self, face: &Face) { self.elements.push(Geometry::Face(face.clone())); }
+            fn free_form_begin(&mut self, element: &Geometry) { self.open = Some(element.clone()); }
+            fn body_statement(&mut self, keyword: &Keyword, _parameters: &std::collections::VecDeque<String>) { self.body.push(keyword.clone()); }
+            fn end(&mut self, element: &Geometry) {
+                assert!(self.open.take().is_some());
+                self.elements.push(element.clone());
+            }
+            fn group(&mut self, grouping: &Grouping) { self.groups.push(grouping.groups.clone()); }
+            fn use_material(&mut self, material: &str) { self.materials.push(material.to_owned()); }
+            fn statement(&mut self, _line: &ObjLine) { self.statements += 1; }
+        }
+
+        for path in ["test_objs/interpreter_test.obj", "test_objs/relative_index_test.obj", "test_objs/body_statements_test.obj", "test_objs/render_state_test.obj"] {
+            let model = ObjInterpreter::load(path).unwrap();
+            let mut collector = Collector::default();
+            ObjParser::new(path).unwrap().visit(&mut collector).unwrap();
+
+            assert_eq!(collector.vertices.iter().collect::<Vec<&Vertex>>(), model.vertex_buffer.vertices().iter().map(|vertex| vertex.as_ref()).collect::<Vec<&Vertex>>());
+            assert_eq!(collector.normals, model.vertex_buffer.normal_count());
+            assert_eq!(collector.elements, model.elements.iter().map(|element| element.geometry.clone()).collect::<Vec<Geometry>>());
+            assert_eq!(collector.materials.last(), model.elements.last().and_then(|element| element.render_state.material.as_ref()));
+        }
+
+        let mut collector = Collector::default();
+        let source = "g left\nusemtl red\nmtllib a.mtl\nv 0 0 0\nv 1 0 0\nv 1 1 0\nf -3 -2 -1\ncstype bezier\ndeg 1\ncurv 0 1 1 2\nparm u 0 1\nend\ng left right\n";
+        ObjParser::from_str(source).visit(&mut collector).unwrap();
+        assert_eq!(collector.groups, vec![vec!["left"], vec!["left", "right"]]);
+        assert_eq!(collector.materials, vec!["red"]);
+        assert_eq!(collector.body, vec![Keyword::ParameterValue]);
+        assert_eq!(collector.statements, 3);
+
+        // Errors match the interpreter
+        for source in ["v 0 0 0\nf 1 2 -3\n", "curv2 1 2\n", "cstype bezier\ndeg 1\ncurv 0 1 1 2\nv 0 0 0\n", "parm u 0 1\n", "vc 1 2 3\n"] {
+            let expected = ObjInterpreter::new(ObjParser::from_str(source)).interpret().unwrap_err();
+            assert_eq!(ObjParser::from_str(source).visit(&mut Collector::default()).unwrap_err(), expected, "{source:?}");
+        }
+    }
+
+    #[test]
+    fn test_parse_options() {
+        let path = "test_objs/lenient_test.obj";
+
+        // Strict parsing stops at the first deviation
+        let error = ObjInterpreter::load(path).unwrap_err();
+        assert_eq!(error.span(), Some(Span::new(3, 3, 1, 12)));
+        assert!(matches!(ObjInterpreter::load_with_options(path, ParseOptions { object_names_with_spaces: true, ..ParseOptions::strict() }).unwrap_err().inner(), ObjError::Interpreter(InterpreterError::VertexData(_))));
+
+        // Lenient parsing recovers, and keeps a warning for each deviation
+        let model = ObjInterpreter::load_with_options(path, ParseOptions::lenient()).unwrap();
+        assert_eq!(model.vertex_buffer.vertex_count(), 3);
+        assert_eq!(model.vertex_buffer.vertices()[1].as_ref(), &Vertex { x: 1.0, y: 0.0, z: 0.0, w: 1.0 });
+        assert_eq!(model.elements[0].grouping.object.as_deref(), Some("My Object"));
+        assert_eq!(model.elements[0].grouping.smoothing_group, None);
+        if let Geometry::Face(face) = &model.elements[0].geometry {
+            assert_eq!(face.references[2], IndexTriplet::new(Index::new(3).unwrap(), Some(Index::new(1).unwrap()), None));
+        } else {
+            panic!("Expected a face");
+        }
+
+        let deviations = vec![
+            (Deviation::ObjectNameWithSpaces, 3),
+            (Deviation::VertexColor, 4),
+            (Deviation::VertexColor, 5),
+            (Deviation::VertexColor, 6),
+            (Deviation::UnknownKeyword, 9),
+            (Deviation::TrailingSlash, 11)
+        ];
+        assert_eq!(model.warnings.iter().map(|warning| (warning.error, warning.span.start_line)).collect::<Vec<(Deviation, usize)>>(), deviations);
+
+        // Each deviation is allowed on its own
+        let source = std::fs::read_to_string(path).unwrap();
+        let options = ParseOptions { vertex_colors: false, ..ParseOptions::lenient() };
+        assert_eq!(ObjInterpreter::with_options(ObjParser::from_str(&source), options).interpret().unwrap_err().span.start_line, 4);
+        let options = ParseOptions { trailing_slashes: false, ..ParseOptions::lenient() };
+        assert_eq!(ObjInterpreter::with_options(ObjParser::from_str(&source), options).interpret().unwrap_err().error, InterpreterError::PolygonalElement(PolygonalElementError::InvalidReference(IndexTripletError::ConversionError)));
+
+        // Keywords from the specification are never skipped
+        let options = ParseOptions::lenient();
+        assert_eq!(ObjInterpreter::with_options(ObjParser::from_str("u 1 2\n"), options).interpret().unwrap_err().error, InterpreterError::UnexpectedKeyword);
+
+        // Parallel loading and visitors report the same warnings
+        assert_eq!(interpret_parallel(&source, 2, options).unwrap(), model);
+
+        struct Warnings(Vec<Spanned<Deviation>>);
+        impl ObjVisitor for Warnings {
+            fn warning(&mut self, warning: Spanned<Deviation>) { self.0.push(warning); }
+        }
+        let mut warnings = Warnings(vec![]);
+        ObjParser::from_str(&source).visit_with_options(&mut warnings, options).unwrap();
+        assert_eq!(warnings.0, model.warnings);
+
+        // These are part of the specification, so strict parsing accepts them
+        let model = ObjInterpreter::new(ObjParser::from_str("#MRGB 0000ff\nvt 0.5\ns off\n")).interpret().unwrap();
+        assert_eq!(model.vertex_buffer.texture_coordinates()[0].u, 0.5);
+        assert!(model.warnings.is_empty());
+    }
+
+    #[test]
+    fn test_diagnostics() {
+        let path = "test_objs/diagnostics_test.obj";
+        let (model, diagnostics) = ObjInterpreter::load_with_diagnostics(path, ParseOptions::strict()).unwrap();
+
+        // Every problem in the file, in order. The body of the bad curve isn't reported with it
+        let expected = vec![
+            (3, "invalid-vertex-data"),
+            (5, "unknown-keyword"),
+            (6, "invalid-reference"),
+            (7, "invalid-grouping"),
+            (10, "invalid-free-form-element"),
+            (13, "invalid-element"),
+            (15, "missing-end"),
+            (18, "unexpected-end")
+        ];
+        assert_eq!(diagnostics.iter().map(|diagnostic| (diagnostic.span.start_line, diagnostic.code.as_str())).collect::<Vec<(usize, &str)>>(), expected);
+        assert!(diagnostics.iter().all(|diagnostic| diagnostic.severity == Severity::Error));
+        assert_eq!(diagnostics[1].code, DiagnosticCode::Interpreter(InterpreterError::UnknownKeyword));
+        assert_eq!(diagnostics[1].to_string(), "line 5, column 1: error[unknown-keyword]: keyword isn't part of the OBJ specification");
+
+        // The rest of the file is still read. The unclosed curve is kept
+        assert_eq!(model.vertex_buffer.vertex_count(), 2);
+        let element_types: Vec<ElementType> = model.elements.iter().map(|element| element.geometry.element_type()).collect();
+        assert_eq!(element_types, vec![ElementType::Curve, ElementType::Line, ElementType::Face]);
+        assert!(model.elements[2].grouping.in_group("parts"));
+
+        // The first diagnostic is the error strict loading stops on
+        let error = ObjInterpreter::new(ObjParser::new(path).unwrap()).interpret().unwrap_err();
+        assert_eq!(Diagnostic::error(error), diagnostics[0]);
+
+        // Warnings are included with lenient parsing
+        let (_, diagnostics) = ObjInterpreter::load_with_diagnostics(path, ParseOptions::lenient()).unwrap();
+        let warnings: Vec<&Diagnostic> = diagnostics.iter().filter(|diagnostic| diagnostic.severity == Severity::Warning).collect();
+        assert_eq!(warnings.iter().map(|warning| (warning.span.start_line, warning.code.as_str())).collect::<Vec<(usize, &str)>>(), vec![(5, "skipped-unknown-keyword"), (13, "trailing-slash")]);
+        assert_eq!(diagnostics.len(), 8);
+
+        // Read errors aren't mistaken for the end of the file. Only the line that isn't UTF-8 is lost
+        let source: &[u8] = b"v 0 0 0\n\xff\xfe\nv 1 1 1\n\xff\nv 2 2 2\n";
+        let (model, diagnostics) = ObjInterpreter::new(ObjParser::from_buf_read(source)).interpret_with_diagnostics();
+        assert_eq!(model.vertex_buffer.vertex_count(), 3);
+        assert_eq!(diagnostics.iter().map(|diagnostic| diagnostic.span).collect::<Vec<Span>>(), vec![Span::line(2), Span::line(4)]);
+        assert_eq!(diagnostics[0], Diagnostic { severity: Severity::Error, span: Span::new(2, 2, 1, 1), code: DiagnosticCode::Interpreter(InterpreterError::IO(std::io::ErrorKind::InvalidData.into())) });
+
+        assert!(matches!(ObjInterpreter::load_with_diagnostics("test_objs/missing.obj", ParseOptions::strict()), Err(ObjError::IO(_))));
+    }
+
+    #[test]
+    fn test_read_errors() {
+        // Read errors aren't mistaken for the end of the file. Lines that aren't UTF-8 are skipped
+        let source: &[u8] = b"v 0 0 0\ng caf\xe9\nv 1 1 1\n";
+        let mut parser = ObjParser::from_buf_read(source);
+        assert!(parser.next().unwrap().is_ok());
+        match parser.next() {
+            Some(Err(error)) => {
+                assert_eq!(error.span(), Some(Span::line(2)));
+                assert!(matches!(error.inner(), ObjError::IO(error) if error.kind() == std::io::ErrorKind::InvalidData));
+            }
+            _                => panic!("Expected a read error")
+        }
+        assert_eq!(parser.next().unwrap().unwrap().span, Span::new(3, 3, 1, 8));
+        assert!(parser.next().is_none());
+
+        let error = ObjInterpreter::new(ObjParser::from_buf_read(source)).interpret().unwrap_err();
+        assert_eq!(error, Spanned::new(InterpreterError::IO(std::io::ErrorKind::InvalidData.into()), Span::line(2)));
+        assert_eq!(ObjParser::from_buf_read(source).visit(&mut Collector).unwrap_err(), error);
+
+        // Errors from the reader itself
+        struct Failing;
+        impl std::io::Read for Failing {
+            fn read(&mut self, _buffer: &mut [u8]) -> std::io::Result<usize> {
+                Err(std::io::Error::other("disk error"))
+            }
+        }
+        struct Collector;
+        impl ObjVisitor for Collector {}
+
+        let reader = std::io::Read::chain(&b"v 0 0 0\nv 1 1 1\n"[..], Failing);
+        let error = ObjInterpreter::new(ObjParser::from_reader(reader)).interpret().unwrap_err();
+        assert_eq!(error, Spanned::new(InterpreterError::IO(std::io::ErrorKind::Other.into()), Span::line(3)));
+        assert_eq!(std::error::Error::source(&error.error).unwrap().to_string(), "disk error");
+
+        // They end the stream for good
+        let mut parser = ObjParser::from_reader(std::io::Read::chain(&b"v 0 0 0\n"[..], Failing));
+        assert!(parser.next().unwrap().is_ok());
+        assert!(parser.next().unwrap().is_err());
+        assert!(parser.next().is_none());
+
+        // Latin-1 names from legacy exporters
+        let source: &[u8] = b"g caf\xe9\nusemtl r\xf4le\nv 0 0 0\np 1\n";
+        let options = ParseOptions { latin1_text: true, ..ParseOptions::strict() };
+        let model = ObjInterpreter::with_options(ObjParser::from_buf_read(source), options).interpret().unwrap();
+        assert!(model.elements[0].grouping.in_group("caf\u{e9}"));
+        assert_eq!(model.elements[0].render_state.material.as_deref(), Some("r\u{f4}le"));
+        assert_eq!(model.warnings, vec![Spanned::new(Deviation::Latin1Text, Span::new(1, 1, 1, 7)), Spanned::new(Deviation::Latin1Text, Span::new(2, 2, 1, 12))]);
+
+        // UTF-8 is still UTF-8 with the fallback on
+        let model = ObjInterpreter::with_options(ObjParser::from_str("g caf\u{e9}\nv 0 0 0\np 1\n"), options).interpret().unwrap();
+        assert!(model.elements[0].grouping.in_group("caf\u{e9}"));
+        assert!(model.warnings.is_empty());
+
+        let library = MaterialLibrary::parse_with_options(&mut ObjParser::from_buf_read(&b"newmtl r\xf4le\nKd 1 0 0\n"[..]), options).unwrap();
+        assert!(library.get("r\u{f4}le").is_some());
+        assert_eq!(library.warnings, vec![Spanned::new(Deviation::Latin1Text, Span::new(1, 1, 1, 12))]);
+        assert_eq!(MaterialLibrary::parse(&mut ObjParser::from_buf_read(&b"newmtl r\xf4le\n"[..])).unwrap_err().error, MaterialError::IO(std::io::ErrorKind::InvalidData.into()));
+    }
+
+    #[test]
+    fn test_bezier_evaluation() {
+        use crate::freeform_geometry::evaluation;
+
+        let model = ObjInterpreter::load("test_objs/bezier_evaluation_test.obj").unwrap();
+        let buffer = &model.vertex_buffer;
+        let curves: Vec<&Curve> = model.elements.iter().filter_map(|element| match &element.geometry {
+            Geometry::Curve(curve) => Some(curve),
+            _                      => None
+        }).collect();
+        let close = |a: [f32; 3], b: [f32; 3]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-4);
+
+        // Point, tangent and curvature of a cubic
+        let derivatives = evaluation::evaluate_curve_derivatives(curves[0], buffer, 0.5, 3).unwrap();
+        assert!(close(derivatives[0], [2.0, 1.5, 0.0]));
+        assert!(close(derivatives[1], [4.5, 0.0, 0.0]));
+        assert!(close(derivatives[2], [0.0, -12.0, 0.0]));
+        assert!(close(derivatives[3], [-12.0, 0.0, 0.0]));
+
+        // Global parameters stretch the same curve over [0, 2]
+        let derivatives = evaluation::evaluate_curve_derivatives(curves[1], buffer, 1.0, 1).unwrap();
+        assert!(close(derivatives[0], [2.0, 1.5, 0.0]));
+        assert!(close(derivatives[1], [2.25, 0.0, 0.0]));
+
+        // Rational curves are weighted. The tangent of a circle is perpendicular to the radius
+        for t in [0.0, 0.25, 0.5, 0.9] {
+            let derivatives = evaluation::evaluate_curve_derivatives(curves[2], buffer, t, 1).unwrap();
+            let ([x, y, _], [dx, dy, _]) = (derivatives[0], derivatives[1]);
+            assert!((x * x + y * y - 1.0).abs() < 1e-4);
+            assert!((x * dx + y * dy).abs() < 1e-4);
+
+            let step = evaluation::evaluate_curve(curves[2], buffer, t + 1e-3).unwrap();
+            assert!((dx - (step[0] - x) / 1e-3).abs() < 1e-2 && (dy - (step[1] - y) / 1e-3).abs() < 1e-2);
+        }
+
+        // Each span covers its own piece of the global parameters
+        let derivatives = evaluation::evaluate_curve_derivatives(curves[3], buffer, 2.0, 2).unwrap();
+        assert!(close(derivatives[0], [1.0, 1.0, 0.0]));
+        assert!(close(derivatives[1], [0.0, 1.0, 0.0]));
+        assert!(close(derivatives[2], [0.0, 0.0, 0.0]));
+        assert!(close(evaluation::evaluate_curve_derivatives(curves[3], buffer, 0.5, 1).unwrap()[1], [1.0, 0.0, 0.0]));
+        assert_eq!(evaluation::evaluate_curve(curves[3], buffer, 3.5), Err(EvaluationError::OutOfRange));
+
+        // Partial derivatives of a surface
+        let surface = match &model.elements[4].geometry {
+            Geometry::Surface(surface) => surface,
+            _                          => panic!("Expected a surface")
+        };
+        let derivatives = evaluation::evaluate_surface_derivatives(surface, buffer, 0.5, 0.5, 2).unwrap();
+        assert!(close(derivatives[0][0], [1.0, 0.5, 0.25]));
+        assert!(close(derivatives[1][0], [2.0, 0.0, 0.5]));
+        assert!(close(derivatives[0][1], [0.0, 1.0, 0.5]));
+        assert!(close(derivatives[1][1], [0.0, 0.0, 1.0]));
+        assert!(close(derivatives[2][0], [0.0, 0.0, 0.0]));
+        assert_eq!(derivatives.iter().map(|row| row.len()).collect::<Vec<usize>>(), vec![3, 2, 1]);
+
+        // Definitions can be evaluated without a model
+        let definition = FreeFormDefinition { form_type: FreeFormType::Bezier, rational: false, degree: Degree::Curve(1) };
+        let points: Vec<HomogeneousPoint> = [[0.0, 0.0, 0.0], [2.0, 0.0, 0.0]].iter().map(|point| evaluation::control_point(&definition, *point, 3.0)).collect();
+        let derivatives = evaluation::curve_derivatives(&definition, &points, &[], 0.5, 1).unwrap();
+        assert_eq!(evaluation::project_curve_derivatives(&derivatives), vec![[1.0, 0.0, 0.0], [2.0, 0.0, 0.0]]);
+        assert_eq!(evaluation::curve_derivatives(&definition, &points, &[0.0, 1.0, 1.0], 0.5, 1), Err(EvaluationError::InvalidParameters));
+        assert_eq!(evaluation::curve_derivatives(&definition, &points, &[0.0, 1.0, 2.0], 0.5, 1), Err(EvaluationError::ControlPointMismatch));
+    }
+
+    #[test]
+    fn test_bspline_evaluation() {
+        use crate::freeform_geometry::evaluation;
+
+        let model = ObjInterpreter::load("test_objs/bspline_evaluation_test.obj").unwrap();
+        let buffer = &model.vertex_buffer;
+        let curves: Vec<&Curve> = model.elements.iter().filter_map(|element| match &element.geometry {
+            Geometry::Curve(curve) => Some(curve),
+            _                      => None
+        }).collect();
+        let close = |a: [f32; 3], b: [f32; 3]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-4);
+
+        // Clamped knots reduce to a bezier curve
+        let derivatives = evaluation::evaluate_curve_derivatives(curves[0], buffer, 0.5, 2).unwrap();
+        assert!(close(derivatives[0], [2.0, 1.5, 0.0]));
+        assert!(close(derivatives[1], [4.5, 0.0, 0.0]));
+        assert!(close(derivatives[2], [0.0, -12.0, 0.0]));
+        assert!(close(evaluation::evaluate_curve(curves[0], buffer, 1.0).unwrap(), [4.0, 0.0, 0.0]));
+
+        // NURBS circle
+        for t in [0.0, 0.3, 0.5, 1.0] {
+            let derivatives = evaluation::evaluate_curve_derivatives(curves[1], buffer, t, 2).unwrap();
+            let ([x, y, _], [dx, dy, _], [ddx, ddy, _]) = (derivatives[0], derivatives[1], derivatives[2]);
+            assert!((x * x + y * y - 1.0).abs() < 1e-4);
+            assert!((x * dx + y * dy).abs() < 1e-4);
+            // Differentiating |C|^2 = 1 twice. |C'|^2 + C.C'' = 0
+            assert!((dx * dx + dy * dy + x * ddx + y * ddy).abs() < 1e-3);
+        }
+
+        // Uniform knots. Only the knots [degree] to [control points] are part of the curve
+        let derivatives = evaluation::evaluate_curve_derivatives(curves[2], buffer, 3.0, 2).unwrap();
+        assert!(close(derivatives[0], [1.5, 0.5, 0.0]));
+        assert!(close(derivatives[1], [1.0, -1.0, 0.0]));
+        assert!(close(derivatives[2], [0.0, 2.0, 0.0]));
+        assert!(close(evaluation::evaluate_curve_derivatives(curves[2], buffer, 2.5, 2).unwrap()[2], [0.0, -2.0, 0.0]));
+        assert!(close(evaluation::evaluate_curve(curves[2], buffer, 4.0).unwrap(), [2.5, 0.5, 0.0]));
+        assert_eq!(evaluation::evaluate_curve(curves[2], buffer, 1.0), Err(EvaluationError::OutOfRange));
+
+        let surface = match &model.elements[3].geometry {
+            Geometry::Surface(surface) => surface,
+            _                          => panic!("Expected a surface")
+        };
+        let derivatives = evaluation::evaluate_surface_derivatives(surface, buffer, 0.5, 0.5, 2).unwrap();
+        assert!(close(derivatives[0][0], [1.0, 0.5, 0.25]));
+        assert!(close(derivatives[1][0], [2.0, 0.0, 0.5]));
+        assert!(close(derivatives[0][1], [0.0, 1.0, 0.5]));
+        assert!(close(derivatives[1][1], [0.0, 0.0, 1.0]));
+
+        // Knot vectors are validated
+        let definition = FreeFormDefinition::new(FreeFormType::BSpline, false, Degree::Curve(1));
+        let points: Vec<HomogeneousPoint> = [[0.0, 0.0, 0.0], [2.0, 0.0, 0.0]].iter().map(|point| evaluation::control_point(&definition, *point, 1.0)).collect();
+        assert_eq!(evaluation::curve_derivatives(&definition, &points, &[], 0.5, 1), Err(EvaluationError::KnotCountMismatch));
+        assert_eq!(evaluation::curve_derivatives(&definition, &points, &[0.0, 0.0, 1.0], 0.5, 1), Err(EvaluationError::KnotCountMismatch));
+        assert_eq!(evaluation::curve_derivatives(&definition, &points, &[0.0, 1.0, 0.5, 1.0], 0.5, 1), Err(EvaluationError::InvalidParameters));
+        assert_eq!(evaluation::curve_derivatives(&definition, &points[..1], &[0.0, 1.0, 2.0], 0.5, 1), Err(EvaluationError::ControlPointMismatch));
+        assert_eq!(evaluation::curve_derivatives(&definition, &points, &[0.0, 0.0, 1.0, 1.0], 0.5, 1).map(|derivatives| evaluation::project_curve_derivatives(&derivatives)), Ok(vec![[1.0, 0.0, 0.0], [2.0, 0.0, 0.0]]));
+    }
+
+    #[test]
+    fn test_cardinal_evaluation() {
+        use crate::freeform_geometry::evaluation::{self, bezier};
+
+        let model = ObjInterpreter::load("test_objs/cardinal_evaluation_test.obj").unwrap();
+        let buffer = &model.vertex_buffer;
+        let close = |a: [f32; 3], b: [f32; 3]| a.iter().zip(b).all(|(a, b)| (a - b).abs() < 1e-4);
+        let (curve, surface) = match (&model.elements[0].geometry, &model.elements[1].geometry) {
+            (Geometry::Curve(curve), Geometry::Surface(surface)) => (curve, surface),
+            _                                                    => panic!("Expected a curve and a surface")
+        };
+
+        // Spans run between the middle control points. Tangents are half the difference of the points around them
+        let derivatives = evaluation::evaluate_curve_derivatives(curve, buffer, 0.0, 1).unwrap();
+        assert!(close(derivatives[0], [1.0, 0.0, 0.0]));
+        assert!(close(derivatives[1], [1.0, 0.5, 0.0]));
+        let derivatives = evaluation::evaluate_curve_derivatives(curve, buffer, 1.0, 1).unwrap();
+        assert!(close(derivatives[0], [2.0, 1.0, 0.0]));
+        assert!(close(derivatives[1], [1.0, 0.0, 0.0]));
+        assert!(close(evaluation::evaluate_curve(curve, buffer, 2.0).unwrap(), [3.0, 0.0, 0.0]));
+
+        // The tangent is continuous between spans
+        let before = evaluation::evaluate_curve_derivatives(curve, buffer, 1.0 - 1e-4, 1).unwrap();
+        assert!(before[1].iter().zip(derivatives[1]).all(|(a, b)| (a - b).abs() < 1e-2));
+
+        // Bezier control points trace the same curve
+        let vertices = evaluation::cardinal_to_bezier(curve, buffer).unwrap();
+        assert_eq!(vertices.len(), 7);
+        assert_eq!((vertices[0].x, vertices[0].y, vertices[3].x, vertices[3].y), (1.0, 0.0, 2.0, 1.0));
+        let points: Vec<HomogeneousPoint> = vertices.iter().map(|vertex| [vertex.x, vertex.y, vertex.z, vertex.w]).collect();
+        for t in [0.25, 0.5, 1.5] {
+            let expected = evaluation::evaluate_curve_derivatives(curve, buffer, t, 2).unwrap();
+            let converted = evaluation::project_curve_derivatives(&bezier::curve_derivatives(&points, 3, &curve.body.parameters_u, t, 2).unwrap());
+            assert!(expected.iter().zip(converted).all(|(a, b)| close(*a, b)));
+        }
+
+        // Linear control points stay linear
+        let derivatives = evaluation::evaluate_surface_derivatives(surface, buffer, 0.5, 0.5, 1).unwrap();
+        assert!(close(derivatives[0][0], [1.5, 1.5, 3.0]));
+        assert!(close(derivatives[1][0], [1.0, 0.0, 1.0]));
+        assert!(close(derivatives[0][1], [0.0, 1.0, 1.0]));
+
+        let definition = FreeFormDefinition::new(FreeFormType::Cardinal, false, Degree::Curve(3));
+        assert_eq!(evaluation::curve_derivatives(&definition, &points[..4], &[0.0, 1.0, 2.0], 0.5, 0), Err(EvaluationError::ControlPointMismatch));
+        assert_eq!(evaluation::cardinal_to_bezier(curve, &VertexBuffer::new()), Err(EvaluationError::InvalidReference(VertexBufferError::BoundsException)));
+    }
+}
@@ -0,0 +1,198 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Mapped Files
+ *      Requires the "memmap" feature.
+ *      The file is mapped into memory, and read without any read calls.
+ *      Pages are only brought in as they are touched, so peak memory stays low on large files.
+ * 
+ *      Mapped files are interpreted straight from the mapping. Each line is split off the mapped
+ *      bytes, checked for UTF-8 on its own, and borrowed without being copied. Only lines joined
+ *      with "\", or decoded as Latin-1, are copied into a buffer.
+ *      ObjParser::map reads through the parser's line buffer instead, so lines can be peeked.
+ * 
+ *      The file must not be modified by another process while it is mapped.
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::fs::File;
+use std::io::{self, BufRead, Read};
+use std::path::Path;
+
+use memmap2::Mmap;
+
+use crate::parser::{ObjLineRef, ObjParser, LineSource};
+use crate::interpreter::ObjInterpreter;
+use crate::model::ObjModel;
+use crate::options::ParseOptions;
+use crate::error::ObjError;
+use crate::span::{Span, Spanned};
+
+pub struct MappedFile {
+    map:      Mmap,
+    position: usize     // Bytes consumed by the parser
+}
+
+impl MappedFile {
+    pub fn open(path: &str) -> io::Result<MappedFile> {
+        let file = File::open(path)?;
+        // Safety: the mapping is read only, and the file is expected to stay unchanged while it's loaded.
+        let map = unsafe { Mmap::map(&file)? };
+        Ok(MappedFile { map, position: 0 })
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.map
+    }
+
+    // The whole file as text. OBJ files must be valid UTF-8.
+    pub fn as_str(&self) -> io::Result<&str> {
+        std::str::from_utf8(&self.map).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))
+    }
+}
+
+// Lines borrowed from bytes already in memory. (ex: a mapped file)
+// Spans and errors match the parser's, line for line.
+pub(crate) struct MappedLines<'a> {
+    data:        &'a [u8],
+    position:    usize,                          // Bytes split off so far
+    line_number: usize,                          // Last physical line split off
+    buffer:      String,                         // Lines joined with "\", or decoded as Latin-1
+    error:       Option<Spanned<io::Error>>,     // Line that isn't UTF-8, waiting to be taken
+    latin1:      bool,                           // Decode lines that aren't UTF-8 as Latin-1
+    latin1_line: bool                            // Line in the buffer was decoded as Latin-1
+}
+
+impl<'a> MappedLines<'a> {
+    pub(crate) fn new(data: &'a [u8], latin1: bool) -> MappedLines<'a> {
+        MappedLines { data, position: 0, line_number: 0, buffer: String::new(), error: None, latin1, latin1_line: false }
+    }
+
+    // Next physical line, with its line feed. None at the end of the data
+    fn physical_line(&mut self) -> Option<&'a [u8]> {
+        let data: &'a [u8] = &self.data[self.position..];
+        if data.is_empty() {
+            return None;
+        }
+
+        let length = data.iter().position(|byte| *byte == b'\n').map_or(data.len(), |offset| offset + 1);
+        self.position += length;
+        self.line_number += 1;
+        Some(&data[..length])
+    }
+
+    // Appends a physical line to the buffer. A line that isn't UTF-8 is kept as an error, unless the fallback is on
+    fn push_line(&mut self, line: &[u8]) -> Option<()> {
+        match std::str::from_utf8(line) {
+            Ok(text)              => self.buffer.push_str(text),
+            Err(_) if self.latin1 => {
+                self.buffer.extend(line.iter().map(|byte| *byte as char));
+                self.latin1_line = true;
+            }
+            Err(error)            => {
+                self.error = Some(Spanned::new(io::Error::new(io::ErrorKind::InvalidData, error), Span::line(self.line_number)));
+                return None;
+            }
+        }
+        Some(())
+    }
+}
+
+impl LineSource for MappedLines<'_> {
+    fn next_line(&mut self) -> Option<(ObjLineRef<'_>, bool)> {
+        const LINE_BREAK: char = '\\';
+
+        if self.error.is_some() {
+            return None;
+        }
+        let physical_line = self.physical_line()?;
+        let start_line = self.line_number;
+
+        // Most lines are borrowed straight from the data
+        if let Ok(text) = std::str::from_utf8(physical_line) {
+            if !text.trim_end().ends_with(LINE_BREAK) {
+                let (start_column, end_column) = columns(text);
+                let mut line = ObjLineRef::from(text);
+                line.span = Span::new(start_line, start_line, start_column, end_column);
+                return Some((line, false));
+            }
+        }
+
+        self.buffer.clear();
+        self.latin1_line = false;
+        self.push_line(physical_line)?;
+        let (start_column, mut end_column) = columns(&self.buffer);
+
+        // The break is dropped, and the next line is joined onto the end of the buffer. (see ObjParser::read_string)
+        while self.buffer.trim_end().ends_with(LINE_BREAK) {
+            let joined = self.buffer.trim_end().trim_end_matches(LINE_BREAK).len();
+            self.buffer.truncate(joined);
+
+            let physical_line = match self.physical_line() {
+                Some(physical_line) => physical_line,
+                None                => break
+            };
+            self.push_line(physical_line)?;
+            end_column = self.buffer[joined..].trim_end().chars().count() + 1;
+        }
+
+        let mut line = ObjLineRef::from(&self.buffer);
+        line.span = Span::new(start_line, self.line_number, start_column, end_column);
+        Some((line, self.latin1_line))
+    }
+
+    fn take_error(&mut self) -> Option<Spanned<io::Error>> {
+        self.error.take()
+    }
+}
+
+// Columns the line starts and ends at, without the whitespace around it
+fn columns(line: &str) -> (usize, usize) {
+    let line = line.trim_end();
+    (line.chars().take_while(|c| c.is_whitespace()).count() + 1, line.chars().count() + 1)
+}
+
+impl Read for MappedFile {
+    fn read(&mut self, buffer: &mut [u8]) -> io::Result<usize> {
+        let read = (&self.map[self.position..]).read(buffer)?;
+        self.position += read;
+        Ok(read)
+    }
+}
+
+impl BufRead for MappedFile {
+    // The mapping is the buffer
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        Ok(&self.map[self.position..])
+    }
+
+    fn consume(&mut self, amount: usize) {
+        self.position = (self.position + amount).min(self.map.len());
+    }
+}
+
+impl ObjParser<MappedFile> {
+    // Maps a file to be parsed.
+    pub fn map(path: &str) -> io::Result<ObjParser<MappedFile>> {
+        Ok(ObjParser::from_buf_read(MappedFile::open(path)?))
+    }
+}
+
+impl ObjInterpreter {
+    // Maps and interprets a file.
+    // Material libraries are loaded relative to the file.
+    pub fn load_mapped(path: &str) -> Result<ObjModel, ObjError> {
+        ObjInterpreter::load_mapped_with_options(path, ParseOptions::default())
+    }
+
+    pub fn load_mapped_with_options(path: &str, options: ParseOptions) -> Result<ObjModel, ObjError> {
+        let file = MappedFile::open(path)?;
+        let mut lines = MappedLines::new(file.as_bytes(), options.latin1_text);
+        let mut model = ObjInterpreter::with_options(ObjParser::from_str(""), options).interpret_lines(&mut lines)?;
+
+        model.load_materials_with_options(Path::new(path).parent().unwrap_or(Path::new("")), options);
+        Ok(model)
+    }
+}
@@ -0,0 +1,170 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * OBJ Model
+ *      The intermediary structure produced by the interpreter.
+ *      Contains the vertex buffer, and the elements that reference it.
+ *      Materials from the referenced material libraries are linked by name.
+ *          - Libraries that can't be loaded are kept, with the reason, instead of failing the model
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::rc::Rc;
+use std::fs::File;
+use std::path::Path;
+
+use crate::parser::ObjParser;
+use crate::span::{Span, Spanned};
+use crate::options::{ParseOptions, Deviation};
+use crate::vertex_buffer::VertexBuffer;
+use crate::element::{Element, ElementType, Geometry};
+use crate::index::Index;
+use crate::freeform_geometry::freeform_elements::{Curve2D, Surface};
+use crate::freeform_geometry::connection::{Connection, ConnectionError};
+use crate::mtl::material::{Material, MaterialError};
+use crate::mtl::material_library::MaterialLibrary;
+
+// Material library that couldn't be loaded
+#[derive(Debug, Clone, PartialEq)]
+pub struct UnresolvedLibrary {
+    pub library: String,        // Name given to "mtllib"
+    pub error:   MaterialError,
+    pub span:    Option<Span>   // Statement in the MTL file that failed. None if the file couldn't be opened
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ObjModel {
+    pub vertex_buffer:        VertexBuffer,
+    pub elements:             Vec<Element>,            // Added with push_element, so surfaces and 2D curves stay indexed
+    pub connections:          Vec<Connection>,         // Seams between surfaces
+    pub material_libraries:   Vec<String>,             // Every file named by "mtllib", in order
+    pub materials:            MaterialLibrary,         // Materials loaded from the material libraries
+    pub unresolved_libraries: Vec<UnresolvedLibrary>,  // Material libraries that couldn't be loaded
+    pub warnings:             Vec<Spanned<Deviation>>, // Deviations from the specification that were recovered from
+    surfaces:                 Vec<usize>,              // Element of each surface, in the order they were read
+    curves_2d:                Vec<usize>               // Element of each 2D curve, in the order they were read
+}
+
+impl ObjModel {
+    pub fn new() -> ObjModel {
+        ObjModel { vertex_buffer: VertexBuffer::new(), elements: vec![], connections: vec![], material_libraries: vec![], materials: MaterialLibrary::new(), unresolved_libraries: vec![], warnings: vec![], surfaces: vec![], curves_2d: vec![] }
+    }
+
+    // Load every material library the model references. Paths are relative to the directory
+    // Libraries that fail are added to unresolved_libraries, and the rest are still loaded
+    pub fn load_materials<P: AsRef<Path>>(&mut self, directory: P) {
+        self.load_materials_with_options(directory, ParseOptions::default())
+    }
+
+    pub fn load_materials_with_options<P: AsRef<Path>>(&mut self, directory: P, options: ParseOptions) {
+        for library in &self.material_libraries {
+            let file = match File::open(directory.as_ref().join(library)) {
+                Ok(file)   => file,
+                Err(error) => {
+                    self.unresolved_libraries.push(UnresolvedLibrary { library: library.clone(), error: MaterialError::IO(error.into()), span: None });
+                    continue;
+                }
+            };
+
+            match MaterialLibrary::parse_with_options(&mut ObjParser::from_reader(file), options) {
+                Ok(materials) => self.materials.extend(materials),
+                Err(error)    => self.unresolved_libraries.push(UnresolvedLibrary { library: library.clone(), error: error.error, span: Some(error.span) })
+            }
+        }
+    }
+
+    // Material active when the element was read
+    pub fn material(&self, element: &Element) -> Option<&Rc<Material>> {
+        self.materials.get(element.render_state.material.as_deref()?)
+    }
+
+    pub fn elements_with_material<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.elements.iter().filter(move |element| {
+            element.render_state.material.as_deref() == Some(name)
+        })
+    }
+
+    pub fn elements_of_type(&self, element_type: ElementType) -> impl Iterator<Item = &Element> {
+        self.elements.iter().filter(move |element| element.element_type() == element_type)
+    }
+
+    // Every group name, in the order they first appear
+    pub fn groups(&self) -> Vec<&str> {
+        let mut groups = Vec::<&str>::new();
+        for element in &self.elements {
+            for group in &element.grouping.groups {
+                if !groups.contains(&group.as_str()) {
+                    groups.push(group);
+                }
+            }
+        }
+        groups
+    }
+
+    // Every object name, in the order they first appear
+    pub fn objects(&self) -> Vec<&str> {
+        let mut objects = Vec::<&str>::new();
+        for object in self.elements.iter().filter_map(|element| element.grouping.object.as_deref()) {
+            if !objects.contains(&object) {
+                objects.push(object);
+            }
+        }
+        objects
+    }
+
+    pub fn elements_in_group<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.elements.iter().filter(move |element| element.grouping.in_group(name))
+    }
+
+    // ex: every face in a group
+    pub fn elements_of_type_in_group<'a>(&'a self, element_type: ElementType, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.elements_in_group(name).filter(move |element| element.element_type() == element_type)
+    }
+
+    pub fn elements_in_object<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Element> {
+        self.elements.iter().filter(move |element| element.grouping.object.as_deref() == Some(name))
+    }
+
+    // Add an element. Surfaces and 2D curves are indexed as they're added, for con, trim, hole and scrv
+    pub fn push_element(&mut self, element: Element) {
+        match element.element_type() {
+            ElementType::Surface => self.surfaces.push(self.elements.len()),
+            ElementType::Curve2D => self.curves_2d.push(self.elements.len()),
+            _                    => ()
+        }
+        self.elements.push(element);
+    }
+
+    // Surface referenced by an index. Surfaces are numbered separately from other elements.
+    pub fn surface(&self, index: Index) -> Option<&Surface> {
+        let element = self.surfaces[index.resolve(self.surfaces.len()).ok()?];
+        match &self.elements.get(element)?.geometry {
+            Geometry::Surface(surface) => Some(surface),
+            _                          => None
+        }
+    }
+
+    // 2D curve referenced by an index, as used by trim, hole, scrv and con
+    pub fn curve_2d(&self, index: Index) -> Option<&Curve2D> {
+        let element = self.curves_2d[index.resolve(self.curves_2d.len()).ok()?];
+        match &self.elements.get(element)?.geometry {
+            Geometry::Curve2D(curve) => Some(curve),
+            _                        => None
+        }
+    }
+
+    pub fn validate_connections(&self) -> Result<(), ConnectionError> {
+        self.connections.iter().try_for_each(|connection| connection.validate(self))
+    }
+
+    // Largest gap along any seam in model space. Finds cracks between connected surfaces.
+    pub fn largest_connection_gap(&self, samples: usize) -> Result<f32, ConnectionError> {
+        let mut gap = 0.0f32;
+        for connection in &self.connections {
+            gap = gap.max(connection.largest_gap(self, samples)?);
+        }
+        Ok(gap)
+    }
+}
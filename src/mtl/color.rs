@@ -0,0 +1,66 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Material Color
+ *      keywords: Ka, Kd, Ks, Tf
+ *      Colors can be given in three forms
+ *          - RGB       "Kd r g b"                  g and b are optional, and equal r when missing
+ *          - Spectral  "Kd spectral file factor"   factor is optional, and defaults to 1.0
+ *          - CIEXYZ    "Kd xyz x y z"              y and z are optional, and equal x when missing
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::collections::VecDeque;
+
+use crate::keywords;
+use crate::utility;
+use super::material::MaterialError;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Color {
+    RGB(f32, f32, f32),
+    Spectral(String, f32),  // File name, multiplier
+    XYZ(f32, f32, f32)
+}
+
+impl Color {
+    pub fn from(parameters: &VecDeque<String>) -> Result<Color, MaterialError> {
+        let mut parameters = parameters.clone();
+
+        match parameters.front().map(|form| form.as_str()) {
+            Some(keywords::COLOR_SPECTRAL) => {
+                parameters.pop_front();
+                let file = parameters.pop_front().ok_or(MaterialError::InvalidParameters)?;
+                let factor = utility::convert_vec::<f32>(&parameters).ok().ok_or(MaterialError::InvalidParameters)?;
+
+                match factor.as_slice() {
+                    []       => Ok(Color::Spectral(file, 1.0)),
+                    [factor] => Ok(Color::Spectral(file, *factor)),
+                    _        => Err(MaterialError::InvalidParameters)
+                }
+            }
+            Some(keywords::COLOR_XYZ) => {
+                parameters.pop_front();
+                let (x, y, z) = Color::parse_triple(&parameters)?;
+                Ok(Color::XYZ(x, y, z))
+            }
+            _ => {
+                let (r, g, b) = Color::parse_triple(&parameters)?;
+                Ok(Color::RGB(r, g, b))
+            }
+        }
+    }
+
+    // One or three components. When only one is given, the others are equal to it.
+    fn parse_triple(parameters: &VecDeque<String>) -> Result<(f32, f32, f32), MaterialError> {
+        let values = utility::convert_vec::<f32>(parameters).ok().ok_or(MaterialError::InvalidParameters)?;
+
+        match values.as_slice() {
+            [a]       => Ok((*a, *a, *a)),
+            [a, b, c] => Ok((*a, *b, *c)),
+            _         => Err(MaterialError::InvalidParameters)
+        }
+    }
+}
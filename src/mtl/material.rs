@@ -0,0 +1,122 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Material
+ *      Everything following a "newmtl" statement up until the next "newmtl"
+ *      Statements that aren't supplied are left as None.
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::collections::VecDeque;
+
+use crate::keywords;
+use crate::error::IoError;
+use super::color::Color;
+use super::texture_map::TextureMap;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum MaterialError {
+    UnknownKeyword,         // Keyword isn't part of the MTL specification
+    MissingMaterial,        // Statement appears before "newmtl"
+    InvalidParameters,
+    InvalidOption,          // Texture map option is unknown, invalid for the map, or has bad arguments
+    MissingFileName,        // Texture map without a file name
+    IO(IoError)             // File or line couldn't be read. (ex: it isn't valid UTF-8)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Dissolve {
+    pub factor: f32,
+    pub halo:   bool        // Dissolve depends on the surface orientation
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct Material {
+    pub name:                   String,
+    // Color & Illumination
+    pub ambient:                Option<Color>,          // Ka
+    pub diffuse:                Option<Color>,          // Kd
+    pub specular:               Option<Color>,          // Ks
+    pub transmission_filter:    Option<Color>,          // Tf
+    pub illumination_model:     Option<u32>,            // illum
+    pub dissolve:               Option<Dissolve>,       // d
+    pub specular_exponent:      Option<f32>,            // Ns
+    pub sharpness:              Option<f32>,            // sharpness
+    pub optical_density:        Option<f32>,            // Ni
+    // Texture Maps
+    pub ambient_map:            Option<TextureMap>,     // map_Ka
+    pub diffuse_map:            Option<TextureMap>,     // map_Kd
+    pub specular_map:           Option<TextureMap>,     // map_Ks
+    pub specular_exponent_map:  Option<TextureMap>,     // map_Ns
+    pub dissolve_map:           Option<TextureMap>,     // map_d
+    pub anti_aliasing:          Option<bool>,           // map_aat
+    pub decal:                  Option<TextureMap>,     // decal
+    pub displacement:           Option<TextureMap>,     // disp
+    pub bump:                   Option<TextureMap>,     // bump
+    pub reflection_maps:        Vec<TextureMap>         // refl. Cube maps have one statement per side
+}
+
+impl Material {
+    pub fn new(name: &str) -> Material {
+        Material { name: name.to_owned(), ..Default::default() }
+    }
+
+    // Apply a single statement to the material. Returns false if the keyword isn't part of the MTL specification.
+    pub fn apply(&mut self, keyword: &str, parameters: &VecDeque<String>) -> Result<bool, MaterialError> {
+        match keyword {
+            keywords::AMBIENT_REFLECTIVITY  => self.ambient             = Some(Color::from(parameters)?),
+            keywords::DIFFUSE_REFLECTIVITY  => self.diffuse             = Some(Color::from(parameters)?),
+            keywords::SPECULAR_REFLECTIVITY => self.specular            = Some(Color::from(parameters)?),
+            keywords::TRANSMISSION_FILTER   => self.transmission_filter = Some(Color::from(parameters)?),
+            keywords::ILLUMINATION_MODEL    => {
+                let model = Material::parse_value::<u32>(parameters)?;
+                if model > 10 {
+                    return Err(MaterialError::InvalidParameters);
+                }
+                self.illumination_model = Some(model);
+            }
+            keywords::DISSOLVE              => self.dissolve            = Some(Material::parse_dissolve(parameters)?),
+            keywords::SPECULAR_EXPONENT     => self.specular_exponent   = Some(Material::parse_value(parameters)?),
+            keywords::SHARPNESS             => self.sharpness           = Some(Material::parse_value(parameters)?),
+            keywords::OPTICAL_DENSITY       => self.optical_density     = Some(Material::parse_value(parameters)?),
+            keywords::AMBIENT_MAP           => self.ambient_map           = Some(TextureMap::from(keyword, parameters)?),
+            keywords::DIFFUSE_MAP           => self.diffuse_map           = Some(TextureMap::from(keyword, parameters)?),
+            keywords::SPECULAR_MAP          => self.specular_map          = Some(TextureMap::from(keyword, parameters)?),
+            keywords::SPECULAR_EXPONENT_MAP => self.specular_exponent_map = Some(TextureMap::from(keyword, parameters)?),
+            keywords::DISSOLVE_MAP          => self.dissolve_map          = Some(TextureMap::from(keyword, parameters)?),
+            keywords::DECAL                 => self.decal                 = Some(TextureMap::from(keyword, parameters)?),
+            keywords::DISPLACEMENT          => self.displacement          = Some(TextureMap::from(keyword, parameters)?),
+            keywords::BUMP                  => self.bump                  = Some(TextureMap::from(keyword, parameters)?),
+            keywords::REFLECTION_MAP        => self.reflection_maps.push(TextureMap::from(keyword, parameters)?),
+            keywords::ANTI_ALIASING         => {
+                match parameters.front().map(|value| value.as_str()) {
+                    Some(keywords::OPTION_ON)  => self.anti_aliasing = Some(true),
+                    Some(keywords::OPTION_OFF) => self.anti_aliasing = Some(false),
+                    _                          => return Err(MaterialError::InvalidParameters)
+                }
+            }
+            _ => return Ok(false)
+        }
+        Ok(true)
+    }
+
+    // "d factor" or "d -halo factor"
+    fn parse_dissolve(parameters: &VecDeque<String>) -> Result<Dissolve, MaterialError> {
+        let mut parameters = parameters.clone();
+        let halo = parameters.front().map(|value| value.as_str()) == Some(keywords::DISSOLVE_HALO);
+        if halo {
+            parameters.pop_front();
+        }
+
+        Ok(Dissolve { factor: Material::parse_value(&parameters)?, halo })
+    }
+
+    fn parse_value<DataT: std::str::FromStr>(parameters: &VecDeque<String>) -> Result<DataT, MaterialError> {
+        if parameters.len() != 1 {
+            return Err(MaterialError::InvalidParameters);
+        }
+        parameters[0].parse::<DataT>().ok().ok_or(MaterialError::InvalidParameters)
+    }
+}
@@ -0,0 +1,128 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Material Library
+ *      A collection of materials read from one or more MTL files.
+ *      Materials are looked up by the name used with "usemtl"
+ *      Exporters add their own statements (ex: "Ke", "Pr", "map_Bump").
+ *      These are an error, unless the parse options allow unknown keywords. Then they're skipped with a warning.
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::rc::Rc;
+use std::io::BufRead;
+use std::path::Path;
+
+use crate::keywords;
+use crate::parser::ObjParser;
+use crate::options::{ParseOptions, Deviation};
+use crate::error::ObjError;
+use crate::span::{Spanned, WithSpan};
+use super::material::{Material, MaterialError};
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct MaterialLibrary {
+    materials:    Vec<Rc<Material>>,
+    pub warnings: Vec<Spanned<Deviation>>   // Statements that were skipped
+}
+
+impl MaterialLibrary {
+    pub fn new() -> MaterialLibrary {
+        MaterialLibrary { materials: vec![], warnings: vec![] }
+    }
+
+    // Loads an MTL file
+    pub fn load<P: AsRef<Path>>(path: P) -> Result<MaterialLibrary, ObjError> {
+        MaterialLibrary::load_with_options(path, ParseOptions::default())
+    }
+
+    // Unknown keywords and the Latin-1 fallback apply to MTL files. The other options are OBJ statements only
+    pub fn load_with_options<P: AsRef<Path>>(path: P, options: ParseOptions) -> Result<MaterialLibrary, ObjError> {
+        let mut parser = ObjParser::from_reader(std::fs::File::open(path)?);
+        Ok(MaterialLibrary::parse_with_options(&mut parser, options)?)
+    }
+
+    pub fn parse<R: BufRead>(parser: &mut ObjParser<R>) -> Result<MaterialLibrary, Spanned<MaterialError>> {
+        MaterialLibrary::parse_with_options(parser, ParseOptions::default())
+    }
+
+    pub fn parse_with_options<R: BufRead>(parser: &mut ObjParser<R>, options: ParseOptions) -> Result<MaterialLibrary, Spanned<MaterialError>> {
+        parser.set_latin1_fallback(options.latin1_text);
+        let mut library = MaterialLibrary::new();
+        let mut current: Option<Material> = None;
+
+        while let Some(line) = parser.get_line() {
+            let keyword = match &line.keyword {
+                Some(keyword) => keyword.as_str(),
+                None          => continue
+            };
+
+            if parser.is_latin1_line() {
+                library.warnings.push(Spanned::new(Deviation::Latin1Text, line.span));
+            }
+
+            if keyword == keywords::NEW_MATERIAL {
+                if let Some(material) = current.take() {
+                    library.add(material);
+                }
+
+                // Material names can't contain spaces, but be forgiving
+                let name = Vec::from(line.parameters).join(" ");
+                if name.is_empty() {
+                    return Err(MaterialError::InvalidParameters).with_span(line.span);
+                }
+                current = Some(Material::new(&name));
+            } else if !keywords::validate_material_keyword(keyword) {
+                // Checked first, so an unknown statement before "newmtl" is still skipped
+                if !options.unknown_keywords {
+                    return Err(MaterialError::UnknownKeyword).with_span(line.span);
+                }
+                library.warnings.push(Spanned::new(Deviation::UnknownKeyword, line.span));
+            } else {
+                let material = current.as_mut().ok_or(MaterialError::MissingMaterial).with_span(line.span)?;
+                material.apply(keyword, &line.parameters).with_span(line.span)?;
+            }
+        }
+
+        if let Some(error) = parser.take_error() {
+            return Err(error.map(|error| MaterialError::IO(error.into())));
+        }
+        if let Some(material) = current.take() {
+            library.add(material);
+        }
+        Ok(library)
+    }
+
+    // Add a material. Replaces any material with the same name
+    pub fn add(&mut self, material: Material) {
+        self.materials.retain(|existing| existing.name != material.name);
+        self.materials.push(Rc::new(material));
+    }
+
+    // Add every material from another library
+    pub fn extend(&mut self, library: MaterialLibrary) {
+        self.warnings.extend(library.warnings);
+        for material in library.materials {
+            self.materials.retain(|existing| existing.name != material.name);
+            self.materials.push(material);
+        }
+    }
+
+    pub fn get(&self, name: &str) -> Option<&Rc<Material>> {
+        self.materials.iter().find(|material| material.name == name)
+    }
+
+    pub fn materials(&self) -> &[Rc<Material>] {
+        &self.materials
+    }
+
+    pub fn len(&self) -> usize {
+        self.materials.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.materials.is_empty()
+    }
+}
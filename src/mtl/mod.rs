@@ -0,0 +1,34 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Materials (MTL)
+ *      Material libraries are referenced by "mtllib", and materials are applied by "usemtl".
+ *      MTL files share the line syntax of OBJ files, so they're read with the same parser.
+ * 
+ *          Material Name                                   ✓
+ *              - newmtl
+ *          Color & Illumination
+ *              - Ka, Kd, Ks, Tf                            ✓
+ *                  * r g b | spectral file.rfl factor | xyz x y z
+ *                  * g and b default to r, y and z default to x
+ *              - illum                                     ✓
+ *              - d, d -halo                                ✓
+ *              - Ns, sharpness, Ni                         ✓
+ *          Texture Maps
+ *              - map_Ka, map_Kd, map_Ks, map_Ns, map_d     ✓
+ *              - map_aat                                   ✓
+ *              - decal, disp, bump                         ✓
+ *              - refl                                      ✓
+ *              - Options
+ *                  * -blendu -blendv -cc -clamp            ✓
+ *                  * -mm -o -s -t -texres                  ✓
+ *                  * -bm -boost -imfchan -type             ✓
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+pub mod color;
+pub mod texture_map;
+pub mod material;
+pub mod material_library;
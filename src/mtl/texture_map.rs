@@ -0,0 +1,199 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Texture Maps
+ *      keywords: map_Ka, map_Kd, map_Ks, map_Ns, map_d, decal, disp, bump, refl
+ *      "map_Kd -option args... filename"
+ * 
+ *      Options come before the file name
+ *          -blendu on|off      -blendv on|off      -cc on|off      -clamp on|off
+ *          -mm base gain       -texres resolution  -bm multiplier  -boost value
+ *          -o u [v [w]]        -s u [v [w]]        -t u [v [w]]
+ *          -imfchan r|g|b|m|l|z
+ *          -type sphere|cube_top|cube_bottom|cube_front|cube_back|cube_left|cube_right
+ *      -bm is only valid for bump maps, and -type is required for reflection maps
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::collections::VecDeque;
+
+use crate::keywords;
+use super::material::MaterialError;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MapChannel {
+    Red,
+    Green,
+    Blue,
+    Matte,
+    Luminance,
+    Depth
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ReflectionType {
+    Sphere,
+    CubeTop,
+    CubeBottom,
+    CubeFront,
+    CubeBack,
+    CubeLeft,
+    CubeRight
+}
+
+impl MapChannel {
+    pub fn from(value: &str) -> Option<MapChannel> {
+        match value {
+            "r" => Some(MapChannel::Red),
+            "g" => Some(MapChannel::Green),
+            "b" => Some(MapChannel::Blue),
+            "m" => Some(MapChannel::Matte),
+            "l" => Some(MapChannel::Luminance),
+            "z" => Some(MapChannel::Depth),
+            _   => None
+        }
+    }
+}
+
+impl ReflectionType {
+    pub fn from(value: &str) -> Option<ReflectionType> {
+        match value {
+            "sphere"      => Some(ReflectionType::Sphere),
+            "cube_top"    => Some(ReflectionType::CubeTop),
+            "cube_bottom" => Some(ReflectionType::CubeBottom),
+            "cube_front"  => Some(ReflectionType::CubeFront),
+            "cube_back"   => Some(ReflectionType::CubeBack),
+            "cube_left"   => Some(ReflectionType::CubeLeft),
+            "cube_right"  => Some(ReflectionType::CubeRight),
+            _             => None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct TextureMapOptions {
+    pub blend_u:            bool,
+    pub blend_v:            bool,
+    pub color_correction:   bool,
+    pub clamp:              bool,
+    pub range:              (f32, f32),         // Base, gain
+    pub offset:             (f32, f32, f32),
+    pub scale:              (f32, f32, f32),
+    pub turbulence:         (f32, f32, f32),
+    pub resolution:         Option<usize>,
+    pub bump_multiplier:    Option<f32>,
+    pub boost:              Option<f32>,
+    pub channel:            Option<MapChannel>,
+    pub reflection_type:    Option<ReflectionType>
+}
+
+impl Default for TextureMapOptions {
+    fn default() -> Self {
+        TextureMapOptions {
+            blend_u:          true,
+            blend_v:          true,
+            color_correction: false,
+            clamp:            false,
+            range:            (0.0, 1.0),
+            offset:           (0.0, 0.0, 0.0),
+            scale:            (1.0, 1.0, 1.0),
+            turbulence:       (0.0, 0.0, 0.0),
+            resolution:       None,
+            bump_multiplier:  None,
+            boost:            None,
+            channel:          None,
+            reflection_type:  None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct TextureMap {
+    pub file:    String,
+    pub options: TextureMapOptions
+}
+
+impl TextureMap {
+    pub fn new(file: &str) -> TextureMap {
+        TextureMap { file: file.to_owned(), options: TextureMapOptions::default() }
+    }
+
+    pub fn from(keyword: &str, parameters: &VecDeque<String>) -> Result<TextureMap, MaterialError> {
+        let mut parameters = parameters.clone();
+        let mut options = TextureMapOptions::default();
+
+        while let Some(option) = parameters.front().filter(|option| option.starts_with('-')).cloned() {
+            parameters.pop_front();
+
+            match option.as_str() {
+                keywords::OPTION_BLEND_U            => options.blend_u = TextureMap::parse_switch(&mut parameters)?,
+                keywords::OPTION_BLEND_V            => options.blend_v = TextureMap::parse_switch(&mut parameters)?,
+                keywords::OPTION_COLOR_CORRECTION   => options.color_correction = TextureMap::parse_switch(&mut parameters)?,
+                keywords::OPTION_CLAMP              => options.clamp = TextureMap::parse_switch(&mut parameters)?,
+                keywords::OPTION_RANGE              => options.range = (TextureMap::parse_value(&mut parameters)?, TextureMap::parse_value(&mut parameters)?),
+                keywords::OPTION_OFFSET             => options.offset = TextureMap::parse_vector(&mut parameters, 0.0)?,
+                keywords::OPTION_SCALE              => options.scale = TextureMap::parse_vector(&mut parameters, 1.0)?,
+                keywords::OPTION_TURBULENCE         => options.turbulence = TextureMap::parse_vector(&mut parameters, 0.0)?,
+                keywords::OPTION_TEXTURE_RESOLUTION => options.resolution = Some(TextureMap::parse_value(&mut parameters)?),
+                keywords::OPTION_BOOST              => options.boost = Some(TextureMap::parse_value(&mut parameters)?),
+                keywords::OPTION_BUMP_MULTIPLIER if keyword == keywords::BUMP => {
+                    options.bump_multiplier = Some(TextureMap::parse_value(&mut parameters)?);
+                }
+                keywords::OPTION_CHANNEL            => {
+                    let channel = parameters.pop_front().ok_or(MaterialError::InvalidOption)?;
+                    options.channel = Some(MapChannel::from(&channel).ok_or(MaterialError::InvalidOption)?);
+                }
+                keywords::OPTION_REFLECTION_TYPE if keyword == keywords::REFLECTION_MAP => {
+                    let reflection_type = parameters.pop_front().ok_or(MaterialError::InvalidOption)?;
+                    options.reflection_type = Some(ReflectionType::from(&reflection_type).ok_or(MaterialError::InvalidOption)?);
+                }
+                _ => return Err(MaterialError::InvalidOption)
+            }
+        }
+
+        if keyword == keywords::REFLECTION_MAP && options.reflection_type.is_none() {
+            return Err(MaterialError::InvalidOption);
+        }
+
+        // File names can contain spaces
+        let file = Vec::from(parameters).join(" ");
+        if file.is_empty() {
+            return Err(MaterialError::MissingFileName);
+        }
+
+        Ok(TextureMap { file, options })
+    }
+
+    fn parse_switch(parameters: &mut VecDeque<String>) -> Result<bool, MaterialError> {
+        match parameters.pop_front().as_deref() {
+            Some(keywords::OPTION_ON)  => Ok(true),
+            Some(keywords::OPTION_OFF) => Ok(false),
+            _                          => Err(MaterialError::InvalidOption)
+        }
+    }
+
+    fn parse_value<DataT: std::str::FromStr>(parameters: &mut VecDeque<String>) -> Result<DataT, MaterialError> {
+        let value = parameters.pop_front().ok_or(MaterialError::InvalidOption)?;
+        value.parse::<DataT>().ok().ok_or(MaterialError::InvalidOption)
+    }
+
+    // "u [v [w]]" Missing components use the default. The file name always remains
+    fn parse_vector(parameters: &mut VecDeque<String>, default: f32) -> Result<(f32, f32, f32), MaterialError> {
+        let mut values = vec![TextureMap::parse_value::<f32>(parameters)?];
+
+        while values.len() < 3 && parameters.len() > 1 {
+            match parameters.front().and_then(|value| value.parse::<f32>().ok()) {
+                Some(value) => {
+                    values.push(value);
+                    parameters.pop_front();
+                }
+                None => break
+            }
+        }
+
+        values.resize(3, default);
+        Ok((values[0], values[1], values[2]))
+    }
+}
@@ -0,0 +1,123 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ *
+ * Parse Options
+ *      Strict parsing follows the specification exactly, anything else is an error.
+ *      Real exporters deviate from it in a handful of common ways. Lenient parsing recovers
+ *      from these, and keeps a warning with the span of the statement instead.
+ *      Each deviation can be allowed on its own. Unknown keywords and Latin-1 text also apply to MTL files.
+ *
+ *          - Trailing slashes on references                "f 1/ 2/ 3/"
+ *          - Keywords that aren't part of the spec         "vc 0.5 0.5 0.5"
+ *          - Vertex colors after the position              "v x y z r g b"
+ *          - Object names containing spaces                "o My Object"
+ *          - Text that isn't UTF-8, decoded as Latin-1     "g caf\xE9"
+ *          - Free-form definitions that don't validate     "cstype cardinal" with "deg 2"
+ *
+ *      Some statements look like deviations, but are part of the specification.
+ *      These are always accepted.
+ *          - Texture coordinates with one component        "vt u"
+ *          - Smoothing groups turned off                   "s off"
+ *          - Comments directly after the "#"               "#MRGB ..."
+ *
+ * ------------------------------------------------------------------------------------*/
+
+use std::fmt;
+use std::collections::VecDeque;
+
+use crate::keywords::{self, Keyword};
+
+// A deviation from the specification that was recovered from
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Deviation {
+    TrailingSlash,              // Reference ends with "/"
+    UnknownKeyword,             // Statement was skipped
+    VertexColor,                // Color was dropped
+    ObjectNameWithSpaces,       // Name was joined back together
+    Latin1Text,                 // Line was decoded as Latin-1
+    InvalidDefinition           // Free-form element was kept, though its definition doesn't validate
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ParseOptions {
+    pub trailing_slashes:         bool,
+    pub unknown_keywords:         bool,
+    pub vertex_colors:            bool,
+    pub object_names_with_spaces: bool,
+    pub latin1_text:              bool,
+    pub invalid_definitions:      bool
+}
+
+impl ParseOptions {
+    // Follow the specification exactly
+    pub fn strict() -> ParseOptions {
+        ParseOptions { trailing_slashes: false, unknown_keywords: false, vertex_colors: false, object_names_with_spaces: false, latin1_text: false, invalid_definitions: false }
+    }
+
+    // Recover from every known deviation
+    pub fn lenient() -> ParseOptions {
+        ParseOptions { trailing_slashes: true, unknown_keywords: true, vertex_colors: true, object_names_with_spaces: true, latin1_text: true, invalid_definitions: true }
+    }
+
+    // Statements with keywords outside of the specification are skipped
+    pub fn skips_keyword(&self, keyword: &str) -> bool {
+        self.unknown_keywords && !keywords::validate_keyword(keyword)
+    }
+
+    // Position of a vertex written with a color. (ex: "x y z r g b" -> "x y z")
+    pub(crate) fn vertex_position<'a>(&self, parameters: &'a str) -> Option<&'a str> {
+        if !self.vertex_colors || parameters.split_whitespace().count() != 6 {
+            return None;
+        }
+
+        // Skip past the first three components
+        let mut colors = parameters;
+        for _ in 0..3 {
+            colors = colors.trim_start();
+            colors = &colors[colors.find(char::is_whitespace).unwrap_or(colors.len())..];
+        }
+        Some(&parameters[..parameters.len() - colors.len()])
+    }
+
+    // Fix the parameters of a statement in place, if it deviates in a way that's allowed
+    pub(crate) fn repair(&self, keyword: &Keyword, parameters: &mut VecDeque<String>) -> Option<Deviation> {
+        match keyword {
+            // Drop trailing slashes from references
+            Keyword::Point | Keyword::Line | Keyword::Face if self.trailing_slashes && parameters.iter().any(|reference| reference.ends_with('/')) => {
+                for reference in parameters.iter_mut() {
+                    let length = reference.trim_end_matches('/').len();
+                    reference.truncate(length);
+                }
+                Some(Deviation::TrailingSlash)
+            }
+            // Join an object name that was split on its spaces
+            Keyword::ObjectName if self.object_names_with_spaces && parameters.len() > 1 => {
+                let name = Vec::from(std::mem::take(parameters)).join(" ");
+                parameters.push_back(name);
+                Some(Deviation::ObjectNameWithSpaces)
+            }
+            _ => None
+        }
+    }
+}
+
+impl Default for ParseOptions {
+    fn default() -> ParseOptions {
+        ParseOptions::strict()
+    }
+}
+
+impl fmt::Display for Deviation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Deviation::TrailingSlash        => write!(f, "reference ends with a \"/\""),
+            Deviation::UnknownKeyword       => write!(f, "statement with an unknown keyword was skipped"),
+            Deviation::VertexColor          => write!(f, "vertex color was ignored"),
+            Deviation::ObjectNameWithSpaces => write!(f, "object name contains spaces"),
+            Deviation::Latin1Text           => write!(f, "line isn't UTF-8, it was decoded as Latin-1"),
+            Deviation::InvalidDefinition    => write!(f, "free-form definition doesn't validate")
+        }
+    }
+}
@@ -0,0 +1,258 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Parallel Loading
+ *      Large files are split into chunks at line boundaries, "\" continuations are kept whole.
+ *      Each chunk is tokenized, and its vertex data and polygonal elements parsed, on its own thread.
+ *      Everything else is rare enough to be interpreted as the chunks are stitched back together.
+ * 
+ *      Chunks are stitched in order, so the model is identical to one read on a single thread.
+ *      Relative indices are resolved while stitching, against the size of the buffers at that
+ *      point in the file. Spans are offset by the lines in the chunks before them.
+ * 
+ *      Chunks are processed a wave at a time, one per thread, to bound memory use.
+ *      Files are read one wave at a time. With the "memmap" feature the file is mapped instead,
+ *      and the chunks are borrowed from the mapping.
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+#[cfg(not(feature = "memmap"))]
+use std::fs::File;
+use std::io::{self, BufRead, Read};
+use std::path::Path;
+use std::{panic, thread};
+
+use crate::keywords::{self, Keyword};
+use crate::options::{ParseOptions, Deviation};
+use crate::parser::{ObjLine, ObjParser};
+use crate::vertex_data::{Vertex, TextureCoordinate, VertexNormal, ParameterSpaceVertex};
+use crate::vertex_buffer::VertexBufferError;
+use crate::polygonal_geometry::polygonal_elements::{Point, Line, Face, PolygonalElementError};
+use crate::interpreter::{ObjInterpreter, InterpreterError};
+use crate::model::ObjModel;
+use crate::error::ObjError;
+use crate::span::{Span, Spanned};
+
+// Bytes read by each thread per wave
+const CHUNK_SIZE: usize = 32 * 1024 * 1024;
+
+// A statement parsed on a worker thread, waiting to be stitched into the model
+#[derive(Debug)]
+pub(crate) enum ParsedStatement {
+    Vertex(Vertex),
+    TextureCoordinate(TextureCoordinate),
+    Normal(VertexNormal),
+    ParameterSpaceVertex(ParameterSpaceVertex),
+    Point(Point),                               // Polygonal elements with unresolved references
+    Line(Line),
+    Face(Face),
+    Statement(ObjLine),                         // Interpreted while stitching
+    Warning(Deviation),                         // Deviation recovered from in the statement next to it
+    Error(InterpreterError)
+}
+
+pub(crate) struct ParsedChunk {
+    pub(crate) statements: Vec<(ParsedStatement, Span)>,    // Spans relative to the start of the chunk
+    pub(crate) lines:      usize                            // Physical lines in the chunk
+}
+
+impl ObjInterpreter {
+    // Loads and interprets a file using every available thread.
+    // Material libraries are loaded relative to the file.
+    pub fn load_parallel(path: &str) -> Result<ObjModel, ObjError> {
+        ObjInterpreter::load_parallel_with_options(path, ParseOptions::default())
+    }
+
+    pub fn load_parallel_with_options(path: &str, options: ParseOptions) -> Result<ObjModel, ObjError> {
+        let threads = thread::available_parallelism().map(|threads| threads.get()).unwrap_or(1);
+        // Chunks are borrowed straight from the mapping when it's available
+        #[cfg(feature = "memmap")]
+        let mut model = interpret_bytes(crate::mapped::MappedFile::open(path)?.as_bytes(), threads, CHUNK_SIZE, options)?;
+        #[cfg(not(feature = "memmap"))]
+        let mut model = interpret_reader(io::BufReader::new(File::open(path)?), threads, CHUNK_SIZE, options)?;
+
+        model.load_materials_with_options(Path::new(path).parent().unwrap_or(Path::new("")), options);
+        Ok(model)
+    }
+}
+
+// Interpret OBJ data already in memory on the given number of threads
+pub fn interpret_parallel(data: &str, threads: usize, options: ParseOptions) -> Result<ObjModel, Spanned<InterpreterError>> {
+    interpret_bytes(data.as_bytes(), threads, CHUNK_SIZE, options)
+}
+
+pub(crate) fn interpret_bytes(data: &[u8], threads: usize, chunk_size: usize, options: ParseOptions) -> Result<ObjModel, Spanned<InterpreterError>> {
+    let mut remaining = data;
+    interpret_waves(threads, options, || {
+        if remaining.is_empty() {
+            return Ok(None);
+        }
+        let (chunk, rest) = remaining.split_at(chunk_boundary(remaining, chunk_size));
+        remaining = rest;
+        Ok(Some(chunk))
+    })
+}
+
+// Only one wave of chunks is held in memory at a time
+pub(crate) fn interpret_reader<R: BufRead>(mut reader: R, threads: usize, chunk_size: usize, options: ParseOptions) -> Result<ObjModel, Spanned<InterpreterError>> {
+    interpret_waves(threads, options, || {
+        let chunk = read_chunk(&mut reader, chunk_size)?;
+        Ok(Some(chunk).filter(|chunk| !chunk.is_empty()))
+    })
+}
+
+// Parses each wave of chunks across the threads, then stitches them into the model. None is the end of the data.
+// A read error ends the data, and is reported after the chunks before it.
+fn interpret_waves<C, F>(threads: usize, options: ParseOptions, mut next_chunk: F) -> Result<ObjModel, Spanned<InterpreterError>>
+where
+    C: AsRef<[u8]> + Sync,
+    F: FnMut() -> io::Result<Option<C>>
+{
+    let threads = threads.max(1);
+    let mut interpreter = ObjInterpreter::with_options(ObjParser::from_str(""), options);
+    let mut line_offset = 0usize;
+    let mut error = None;
+    let mut finished = false;
+
+    while !finished {
+        // Split off one wave of chunks
+        let mut chunks = Vec::<C>::with_capacity(threads);
+        while chunks.len() < threads && !finished {
+            match next_chunk() {
+                Ok(Some(chunk)) => chunks.push(chunk),
+                Ok(None)        => finished = true,
+                Err(read_error) => {
+                    error = Some(read_error);
+                    finished = true;
+                }
+            }
+        }
+
+        // A panic on a worker is passed on to the caller as it was
+        let parsed: Vec<ParsedChunk> = thread::scope(|scope| {
+            let workers: Vec<_> = chunks.iter().map(|chunk| scope.spawn(move || parse_chunk(chunk.as_ref(), options))).collect();
+            workers.into_iter().map(|worker| worker.join().unwrap_or_else(|payload| panic::resume_unwind(payload))).collect()
+        });
+
+        for chunk in parsed {
+            for (statement, span) in chunk.statements {
+                let span = Span::new(span.start_line + line_offset, span.end_line + line_offset, span.start_column, span.end_column);
+                interpreter.interpret_parsed(statement, span)?;
+            }
+            line_offset += chunk.lines;
+        }
+    }
+
+    if let Some(error) = error {
+        return Err(Spanned::new(InterpreterError::IO(error.into()), Span::line(line_offset + 1)));
+    }
+    interpreter.finish()
+}
+
+// First line boundary at or after size. A line ending in "\" continues onto the next one.
+pub(crate) fn chunk_boundary(data: &[u8], size: usize) -> usize {
+    let mut position = size;
+
+    while position < data.len() {
+        let end = match data[position..].iter().position(|byte| *byte == b'\n') {
+            Some(offset) => position + offset,
+            None         => return data.len()
+        };
+
+        let start = data[..end].iter().rposition(|byte| *byte == b'\n').map_or(0, |offset| offset + 1);
+        if !continues(&data[start..end]) {
+            return end + 1;
+        }
+        position = end + 1;
+    }
+    data.len()
+}
+
+// Reads at least size bytes, then up to the next line boundary. Empty at the end of the data
+pub(crate) fn read_chunk<R: BufRead>(reader: &mut R, size: usize) -> io::Result<Vec<u8>> {
+    let mut chunk = Vec::<u8>::with_capacity(size);
+    reader.by_ref().take(size as u64).read_to_end(&mut chunk)?;
+
+    // Finish the last line, and every line it continues onto
+    loop {
+        if let Some(data) = chunk.strip_suffix(b"\n") {
+            let start = data.iter().rposition(|byte| *byte == b'\n').map_or(0, |offset| offset + 1);
+            if !continues(&data[start..]) {
+                break;
+            }
+        }
+        if reader.read_until(b'\n', &mut chunk)? == 0 {
+            break;
+        }
+    }
+    Ok(chunk)
+}
+
+// The line, without its line feed, ends in "\"
+fn continues(line: &[u8]) -> bool {
+    line.iter().rposition(|byte| !byte.is_ascii_whitespace()).is_some_and(|last| line[last] == b'\\')
+}
+
+pub(crate) fn parse_chunk(chunk: &[u8], options: ParseOptions) -> ParsedChunk {
+    let mut parser = ObjParser::from_buf_read(chunk);
+    parser.set_latin1_fallback(options.latin1_text);
+    let mut statements = Vec::<(ParsedStatement, Span)>::new();
+
+    loop {
+        while let Some(line) = parser.get_line_ref() {
+            let vertex_data = |parsed: Option<ParsedStatement>| parsed.unwrap_or(ParsedStatement::Error(InterpreterError::VertexData(VertexBufferError::InterpreterError)));
+            let polygonal_element = |parsed: Result<ParsedStatement, PolygonalElementError>| parsed.unwrap_or_else(|error| ParsedStatement::Error(InterpreterError::PolygonalElement(error)));
+
+            let statement = match line.keyword {
+                None                                   => {
+                    let span = line.span;
+                    if parser.is_latin1_line() {
+                        statements.push((ParsedStatement::Warning(Deviation::Latin1Text), span));
+                    }
+                    continue;
+                }
+                Some(keywords::VERTEX)                 => {
+                    // Vertices written with a color keep only their position, when the options allow it
+                    let vertex = Vertex::parse(line.parameters).or_else(|| {
+                        let vertex = options.vertex_position(line.parameters).and_then(Vertex::parse)?;
+                        statements.push((ParsedStatement::Warning(Deviation::VertexColor), line.span));
+                        Some(vertex)
+                    });
+                    vertex_data(vertex.map(ParsedStatement::Vertex))
+                }
+                Some(keywords::TEXTURE_COORDINATE)     => vertex_data(TextureCoordinate::parse(line.parameters).map(ParsedStatement::TextureCoordinate)),
+                Some(keywords::VERTEX_NORMAL)          => vertex_data(VertexNormal::parse(line.parameters).map(ParsedStatement::Normal)),
+                Some(keywords::PARAMETER_SPACE_VERTEX) => vertex_data(ParameterSpaceVertex::parse(line.parameters).map(ParsedStatement::ParameterSpaceVertex)),
+                Some(keyword @ (keywords::POINT | keywords::LINE | keywords::FACE)) => {
+                    let keyword = Keyword::from(keyword);
+                    let mut parameters = line.to_line().parameters;
+                    if let Some(deviation) = options.repair(&keyword, &mut parameters) {
+                        statements.push((ParsedStatement::Warning(deviation), line.span));
+                    }
+
+                    match keyword {
+                        Keyword::Point => polygonal_element(Point::from(&parameters).map(ParsedStatement::Point)),
+                        Keyword::Line  => polygonal_element(Line::from(&parameters).map(ParsedStatement::Line)),
+                        _              => polygonal_element(Face::from(&parameters).map(ParsedStatement::Face))
+                    }
+                }
+                Some(_)                                => ParsedStatement::Statement(line.to_line())
+            };
+            let span = line.span;
+            statements.push((statement, span));
+
+            if parser.is_latin1_line() {
+                statements.push((ParsedStatement::Warning(Deviation::Latin1Text), span));
+            }
+        }
+
+        // A line that isn't UTF-8 stops the parser. Reading carries on past it, unless the stream ended
+        match parser.take_error() {
+            Some(error) => statements.push((ParsedStatement::Error(InterpreterError::IO(error.error.into())), error.span)),
+            None        => break
+        }
+    }
+    ParsedChunk { statements, lines: parser.line_number() }
+}
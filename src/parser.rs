@@ -4,114 +4,313 @@
  * Last Edit: Zatarita (5/11/2023)
  * 
  * OBJ Parsing & Line Data
+ *      The parser accepts any buffered reader. Files, in-memory strings, network buffers, etc.
+ *      Peeking holds on to the next line rather than seeking, so streams like stdin work.
+ *      Every line remembers the span it was read from, including "\" continuations.
+ *      Lines are read into one reused buffer. ObjLineRef borrows from it without allocating,
+ *      ObjLine is an owned copy for convenience.
+ * 
+ *      Read errors stop the stream, but aren't mistaken for the end of the file. They're kept
+ *      with the span of the line that couldn't be read. Files must be UTF-8, unless the
+ *      Latin-1 fallback is on. Lines that aren't valid UTF-8 are then decoded as Latin-1.
+ *      Otherwise only that line is lost. The stream carries on after its error is taken.
+ *      Any other read error ends the stream for good.
  * 
  * ------------------------------------------------------------------------------------*/
 
 use std::fs::File;
-use std::io::{BufReader, BufRead, Seek, SeekFrom};
+use std::io::{self, BufReader, BufRead, Read};
 use std::collections::VecDeque;
 
-use crate::keywords;
+use crate::keywords::{self, Keyword};
+use crate::span::{Span, Spanned};
+use crate::error::ObjError;
 
 // Represents a single parsed line from an obj file
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub struct ObjLine {
-    pub keyword:    Option<String>,     // Keywords define the function of the line. Can be empty if comment
+    pub keyword:    Option<Keyword>,    // Keywords define the function of the line. Can be empty if comment
     pub parameters: VecDeque<String>,   // Parameter(s) when available supply additional information for keywords
-    pub comment:    Option<String>      // Comments can appear at any point in the file.
+    pub comment:    Option<String>,     // Comments can appear at any point in the file.
+    pub span:       Span                // Where the line was read from. Default when not read from a parser
 }
 
-impl ObjLine {
-    #[allow(unused_assignments)]
-    pub fn from(line: &String) -> ObjLine {
-        let mut keyword:    Option<String>   = None;
-        let mut parameters: VecDeque<String> = VecDeque::<String>::new();
-        let mut comment:    Option<String>   = None;
-
-        if let Some( (data, line_comment) ) = line.trim_end().split_once(keywords::COMMENT) {
-            let mut line_elements: VecDeque<String> = data.split_ascii_whitespace().map(|s| s.to_owned() ).collect();
-            
-            if !line_comment.is_empty() {
-                comment = Some(line_comment.to_owned());
-            }
-            keyword = line_elements.pop_front();
-            parameters = line_elements;
+// A single line borrowed from the text it was read from. Nothing is allocated.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ObjLineRef<'a> {
+    pub keyword:    Option<&'a str>,
+    pub parameters: &'a str,            // Everything between the keyword and the comment
+    pub comment:    Option<&'a str>,
+    pub span:       Span
+}
 
-        } else {
-            let mut line_elements: VecDeque<String> = line.split_ascii_whitespace().map(|s| s.to_owned() ).collect();
+impl<'a> ObjLineRef<'a> {
+    pub fn from(line: &'a str) -> ObjLineRef<'a> {
+        let (data, comment) = match line.trim_end().split_once(keywords::COMMENT) {
+            Some( (data, comment) ) => (data, Some(comment).filter(|comment| !comment.is_empty())),
+            None                    => (line, None)
+        };
+
+        let data = data.trim_start();
+        let (keyword, parameters) = match data.split_once(|c: char| c.is_ascii_whitespace()) {
+            Some( (keyword, parameters) ) => (keyword, parameters.trim()),
+            None                          => (data.trim_end(), "")
+        };
+
+        ObjLineRef { keyword: Some(keyword).filter(|keyword| !keyword.is_empty()), parameters, comment, span: Span::default() }
+    }
+
+    pub fn parameters(&self) -> impl Iterator<Item = &'a str> {
+        self.parameters.split_ascii_whitespace()
+    }
+
+    // Unknown keywords are copied into the Keyword
+    pub fn keyword(&self) -> Option<Keyword> {
+        self.keyword.map(Keyword::from)
+    }
 
-            keyword = line_elements.pop_front();
-            parameters = line_elements;
+    // Owned copy of the line
+    pub fn to_line(&self) -> ObjLine {
+        ObjLine {
+            keyword:    self.keyword(),
+            parameters: self.parameters().map(|s| s.to_owned()).collect(),
+            comment:    self.comment.map(|s| s.to_owned()),
+            span:       self.span
         }
+    }
+}
+
+impl ObjLine {
+    pub fn from(line: &str) -> ObjLine {
+        ObjLineRef::from(line).to_line()
+    }
+}
 
-        ObjLine { keyword, parameters, comment }
+// Lines are equal when they hold the same statement, regardless of where they were read from
+impl PartialEq for ObjLine {
+    fn eq(&self, other: &Self) -> bool {
+        self.keyword == other.keyword && self.parameters == other.parameters && self.comment == other.comment
     }
 }
 
-pub struct ObjParser {
-    reader: BufReader<File>
+// Where the interpreter reads its lines from. (ex: the parser, or a mapped file. see mapped.rs)
+pub(crate) trait LineSource {
+    // Borrow the next line, and whether it was decoded as Latin-1. None at the end, or while a read error is pending
+    fn next_line(&mut self) -> Option<(ObjLineRef<'_>, bool)>;
+    // The read error that stopped the lines early
+    fn take_error(&mut self) -> Option<Spanned<io::Error>>;
+}
+
+pub struct ObjParser<R: BufRead = BufReader<File>> {
+    reader:      R,
+    buffer:      String,                         // Current line. Reused for every line read
+    pending:     Option<Option<Span>>,           // Line in the buffer read ahead by peek_line. Inner None is EOF
+    line_number: usize,                          // Last physical line read from the reader
+    error:       Option<Spanned<io::Error>>,     // Read error that stopped the stream early
+    failed:      bool,                           // Nothing more is read after a read error, other than invalid UTF-8
+    raw:         Vec<u8>,                        // Undecoded line, for the Latin-1 fallback
+    latin1:      bool,                           // Decode lines that aren't UTF-8 as Latin-1
+    latin1_line: bool                            // Current line was decoded as Latin-1
 }
 
 impl ObjParser {
     // Loads a file to be parsed.
     pub fn new(path: &str) -> std::io::Result<ObjParser> {
-        Ok(ObjParser { reader: BufReader::new(File::open(path)?) })
+        Ok(ObjParser::from_buf_read(BufReader::new(File::open(path)?)))
+    }
+}
+
+impl<T: Read> ObjParser<BufReader<T>> {
+    // Parse from any reader, buffering it.
+    pub fn from_reader(reader: T) -> ObjParser<BufReader<T>> {
+        ObjParser::from_buf_read(BufReader::new(reader))
+    }
+}
+
+impl<'a> ObjParser<&'a [u8]> {
+    // Parse from a string already in memory. (ex: include_str!)
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(data: &'a str) -> ObjParser<&'a [u8]> {
+        ObjParser::from_buf_read(data.as_bytes())
+    }
+}
+
+impl<R: BufRead> ObjParser<R> {
+    // Parse from a reader that is already buffered.
+    pub fn from_buf_read(reader: R) -> ObjParser<R> {
+        ObjParser { reader, buffer: String::new(), pending: None, line_number: 0, error: None, failed: false, raw: vec![], latin1: false, latin1_line: false }
     }
 
-    // Gets the next line from the file accounting for potential line breaks.
-    fn read_string(&mut self) -> Option<String> {
+    // Reads the next line from the stream into the buffer accounting for potential line breaks.
+    fn read_string(&mut self) -> Option<Span> {
         const LINE_BREAK: char = '\\';
 
-        let mut buf:    String = String::new();
-        let bytes_read: usize  = self.reader.read_line(&mut buf).ok()?;
+        self.buffer.clear();
+        self.latin1_line = false;
+        let bytes_read: usize = self.read_physical_line()?;
 
         // Check EOF
         if bytes_read == 0 {
             return None;
         }
 
+        self.line_number += 1;
+        let start_line:   usize = self.line_number;
+        let start_column: usize = self.buffer.trim_end().chars().take_while(|c| c.is_whitespace()).count() + 1;
+        let mut end_column: usize = self.buffer.trim_end().chars().count() + 1;
+
         // Lines can be split using "\". If this is the case, the next line is a part of the current line.
-        // We need to read the next line, and concat them together.
-        while buf.trim_end().ends_with(LINE_BREAK) {
-            let next_line = self.read_string()?;
+        // The break is dropped, and the next line is read onto the end of the buffer.
+        while self.buffer.trim_end().ends_with(LINE_BREAK) {
+            let joined: usize = self.buffer.trim_end().trim_end_matches(LINE_BREAK).len();
+            self.buffer.truncate(joined);
 
-            buf = format!("{}{next_line}", buf.trim_end().trim_end_matches(LINE_BREAK));
+            if self.read_physical_line()? == 0 {
+                break;
+            }
+            self.line_number += 1;
+            end_column = self.buffer[joined..].trim_end().chars().count() + 1;
         }
 
-        Some(buf)
+        Some(Span::new(start_line, self.line_number, start_column, end_column))
+    }
+
+    // Appends the next physical line to the buffer. Read errors stop the stream, and are kept.
+    fn read_physical_line(&mut self) -> Option<usize> {
+        if self.failed || self.error.is_some() {
+            return None;
+        }
+        if self.latin1 {
+            return self.read_latin1_line();
+        }
+
+        match self.reader.read_line(&mut self.buffer) {
+            Ok(bytes_read) => Some(bytes_read),
+            Err(error)     => {
+                self.fail(error);
+                None
+            }
+        }
+    }
+
+    // Keep a read error. A line that isn't UTF-8 has still been read past, so the stream can carry on after it
+    fn fail(&mut self, error: io::Error) {
+        let line = self.line_number + 1;
+        if error.kind() == io::ErrorKind::InvalidData {
+            self.line_number = line;
+        } else {
+            self.failed = true;
+        }
+        self.error = Some(Spanned::new(error, Span::line(line)));
+    }
+
+    // Latin-1 maps every byte straight to the code point of the same value
+    fn read_latin1_line(&mut self) -> Option<usize> {
+        self.raw.clear();
+        let bytes_read = match self.reader.read_until(b'\n', &mut self.raw) {
+            Ok(bytes_read) => bytes_read,
+            Err(error)     => {
+                self.fail(error);
+                return None;
+            }
+        };
+
+        match std::str::from_utf8(&self.raw) {
+            Ok(line) => self.buffer.push_str(line),
+            Err(_)   => {
+                self.buffer.extend(self.raw.iter().map(|byte| *byte as char));
+                self.latin1_line = true;
+            }
+        }
+        Some(bytes_read)
+    }
+
+    // Borrow the next line from the stream. Progresses the stream
+    // The line is only valid until the next line is read.
+    pub fn get_line_ref(&mut self) -> Option<ObjLineRef<'_>> {
+        self.next_line().map(|(line, _)| line)
     }
 
     // Parse a line read from the stream. Progresses the stream
     pub fn get_line(&mut self) -> Option<ObjLine> {
-        let line: String = self.read_string()?;                    
-        let parsed_line: ObjLine = ObjLine::from(&line);
-        Some(parsed_line)                              
+        self.get_line_ref().map(|line| line.to_line())
+    }
+
+    // The read error that stopped the stream, if it didn't stop at the end of the file
+    // The span is the physical line that couldn't be read. Reading carries on past lines that aren't UTF-8
+    pub fn take_error(&mut self) -> Option<Spanned<io::Error>> {
+        self.error.take()
+    }
+
+    // Decode lines that aren't valid UTF-8 as Latin-1, rather than failing. (ex: names from legacy exporters)
+    pub fn set_latin1_fallback(&mut self, enabled: bool) {
+        self.latin1 = enabled;
+    }
+
+    // The last line read wasn't valid UTF-8, and was decoded as Latin-1
+    pub fn is_latin1_line(&self) -> bool {
+        self.latin1_line
+    }
+
+    // Last physical line read from the stream
+    pub fn line_number(&self) -> usize {
+        self.line_number
     }
 
     // Ignore a line
-    pub fn skip_line(&mut self) -> () {
-        self.get_line();
+    pub fn skip_line(&mut self) {
+        if self.pending.take().is_none() {
+            self.read_string();
+        }
     }
 
-    // Parse a line read from stream. Resets stream position
+    // Borrow a line read from stream. The line is kept, and returned by the next call to get_line
+    pub fn peek_line_ref(&mut self) -> Option<ObjLineRef<'_>> {
+        if self.pending.is_none() {
+            self.pending = Some(self.read_string());
+        }
+
+        let mut line = ObjLineRef::from(&self.buffer);
+        line.span = self.pending.flatten()?;
+        Some(line)
+    }
+
+    // Parse a line read from stream. The line is kept, and returned by the next call to get_line
     pub fn peek_line(&mut self) -> Option<ObjLine> {
-        let current_pos: u64 = self.reader.seek(SeekFrom::Current(0)).ok()?;
-        let line: Option<ObjLine> = self.get_line();
-        let _ = self.reader.seek(SeekFrom::Start(current_pos));
-        line                            
+        self.peek_line_ref().map(|line| line.to_line())
+    }
+
+    // Get the next keyword from stream, without progressing the stream
+    pub fn get_next_keyword(&mut self) -> Option<Keyword> {
+        self.peek_line_ref()?.keyword()
+    }
+}
+
+impl<R: BufRead> LineSource for ObjParser<R> {
+    fn next_line(&mut self) -> Option<(ObjLineRef<'_>, bool)> {
+        let span = match self.pending.take() {
+            Some(pending) => pending?,
+            None          => self.read_string()?
+        };
+
+        let mut line = ObjLineRef::from(&self.buffer);
+        line.span = span;
+        Some((line, self.latin1_line))
     }
 
-    // Get the next keyword from stream, resets stream position
-    pub fn get_next_keyword(&mut self) -> Option<String> {
-        let line: ObjLine = self.peek_line()?;
-        line.keyword
+    fn take_error(&mut self) -> Option<Spanned<io::Error>> {
+        self.error.take()
     }
 }
 
-impl Iterator for ObjParser {
-    type Item = ObjLine;
+// Read errors are returned in place of the line. Lines that aren't UTF-8 are skipped, other errors end the stream
+impl<R: BufRead> Iterator for ObjParser<R> {
+    type Item = Result<ObjLine, ObjError>;
 
     fn next(&mut self) -> Option<Self::Item> {
-        self.get_line()                                 
+        match self.get_line() {
+            Some(line) => Some(Ok(line)),
+            None       => self.take_error().map(|error| Err(error.into()))
+        }
     }
 }
\ No newline at end of file
@@ -0,0 +1,102 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Index Triplet
+ *      A single corner of a polygonal element.
+ *      References a vertex, and optionally a texture coordinate and a normal.
+ *          v | v/vt | v//vn | v/vt/vn
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::index::{Index, IndexError};
+use crate::vertex_buffer::VertexCounts;
+use crate::utility;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum IndexTripletError {
+    InvalidFormat,              // Too many components, or missing required components
+    InvalidIndex(IndexError),   // Index is zero
+    ConversionError             // Component isn't an integer
+}
+
+// The form a reference was written in
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TripletFormat {
+    Vertex,                     // v
+    VertexTexture,              // v/vt
+    VertexNormal,               // v//vn
+    VertexTextureNormal         // v/vt/vn
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct IndexTriplet {
+    pub vertex:             Index,
+    pub texture_coordinate: Option<Index>,
+    pub normal:             Option<Index>
+}
+
+impl IndexTriplet {
+    pub fn new(vertex: Index, texture_coordinate: Option<Index>, normal: Option<Index>) -> IndexTriplet {
+        IndexTriplet { vertex, texture_coordinate, normal }
+    }
+
+    pub fn format(&self) -> TripletFormat {
+        match (self.texture_coordinate, self.normal) {
+            (None,    None)    => TripletFormat::Vertex,
+            (Some(_), None)    => TripletFormat::VertexTexture,
+            (None,    Some(_)) => TripletFormat::VertexNormal,
+            (Some(_), Some(_)) => TripletFormat::VertexTextureNormal
+        }
+    }
+
+    // Convert relative indices to absolute indices using the current size of the buffers
+    pub fn resolve(&self, counts: &VertexCounts) -> Result<IndexTriplet, IndexError> {
+        Ok(IndexTriplet {
+            vertex:             self.vertex.to_absolute(counts.vertices)?,
+            texture_coordinate: self.texture_coordinate.map(|index| index.to_absolute(counts.texture_coordinates)).transpose()?,
+            normal:             self.normal.map(|index| index.to_absolute(counts.normals)).transpose()?
+        })
+    }
+
+    fn parse_index(component: &str) -> Result<Index, IndexTripletError> {
+        let value = utility::parse_isize(component).ok_or(IndexTripletError::ConversionError)?;
+        Index::new(value).map_err(IndexTripletError::InvalidIndex)
+    }
+}
+
+impl FromStr for IndexTriplet {
+    type Err = IndexTripletError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut components = s.split('/');
+        let v = components.next().ok_or(IndexTripletError::InvalidFormat)?;
+        let (vt, vn) = (components.next(), components.next());
+        if components.next().is_some() {
+            return Err(IndexTripletError::InvalidFormat);
+        }
+
+        match (vt, vn) {
+            (None,     None)     => Ok(IndexTriplet::new(IndexTriplet::parse_index(v)?, None, None)),
+            (Some(vt), None)     => Ok(IndexTriplet::new(IndexTriplet::parse_index(v)?, Some(IndexTriplet::parse_index(vt)?), None)),
+            (Some(""), Some(vn)) => Ok(IndexTriplet::new(IndexTriplet::parse_index(v)?, None, Some(IndexTriplet::parse_index(vn)?))),
+            (Some(vt), Some(vn)) => Ok(IndexTriplet::new(IndexTriplet::parse_index(v)?, Some(IndexTriplet::parse_index(vt)?), Some(IndexTriplet::parse_index(vn)?))),
+            (None,     Some(_))  => Err(IndexTripletError::InvalidFormat)
+        }
+    }
+}
+
+impl fmt::Display for IndexTriplet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.texture_coordinate, self.normal) {
+            (None,     None)         => write!(f, "{}", self.vertex),
+            (Some(vt), None)         => write!(f, "{}/{vt}", self.vertex),
+            (None,     Some(vn))     => write!(f, "{}//{vn}", self.vertex),
+            (Some(vt), Some(vn))     => write!(f, "{}/{vt}/{vn}", self.vertex)
+        }
+    }
+}
@@ -0,0 +1,22 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Polygonal Geometry
+ *      Elements
+ *          - Points     "p  v1 v2 v3 ..."
+ *              * Vertex references only
+ *          - Lines      "l  v1/vt1 v2/vt2 ..."
+ *              * At least two references
+ *              * Texture coordinates are optional
+ *          - Faces      "f  v1/vt1/vn1 v2/vt2/vn2 v3/vt3/vn3 ..."
+ *              * At least three references
+ *              * Texture coordinates and normals are optional
+ *      Every reference in an element must use the same form.
+ *          v | v/vt | v//vn | v/vt/vn
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+pub mod index_triplet;
+pub mod polygonal_elements;
@@ -0,0 +1,85 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Polygonal Elements
+ *      Points, Lines, and Faces.
+ *      Each element holds one index triplet per corner.
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::collections::VecDeque;
+
+use crate::index::IndexError;
+use crate::vertex_buffer::VertexCounts;
+use super::index_triplet::{IndexTriplet, IndexTripletError, TripletFormat};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PolygonalElementError {
+    InvalidReference(IndexTripletError),    // Reference couldn't be parsed
+    MixedReferenceFormats,                  // All references in an element must use the same form
+    InvalidReferenceFormat,                 // Form isn't allowed for this element (ex: normals on a line)
+    NotEnoughReferences                     // Lines need 2 references, faces need 3
+}
+
+// Parse every reference, making sure they all share the same form, and that the form is allowed
+fn parse_references(parameters: &VecDeque<String>, minimum: usize, allowed: &[TripletFormat]) -> Result<Vec<IndexTriplet>, PolygonalElementError> {
+    if parameters.len() < minimum {
+        return Err(PolygonalElementError::NotEnoughReferences);
+    }
+
+    let mut references = Vec::<IndexTriplet>::with_capacity(parameters.len());
+    for parameter in parameters {
+        let reference = parameter.parse::<IndexTriplet>().map_err(PolygonalElementError::InvalidReference)?;
+
+        if let Some(first) = references.first() {
+            if first.format() != reference.format() {
+                return Err(PolygonalElementError::MixedReferenceFormats);
+            }
+        } else if !allowed.contains(&reference.format()) {
+            return Err(PolygonalElementError::InvalidReferenceFormat);
+        }
+
+        references.push(reference);
+    }
+
+    Ok(references)
+}
+
+macro_rules! polygonal_element {
+    ( $struct_name:ident, $minimum:expr, $( $format:ident ),* ) => {
+        #[derive(Debug, Clone, Default, PartialEq)]
+        pub struct $struct_name {
+            pub references: Vec<IndexTriplet>
+        }
+
+        impl $struct_name {
+            pub fn new(references: Vec<IndexTriplet>) -> $struct_name {
+                $struct_name { references }
+            }
+
+            pub fn from(parameters: &VecDeque<String>) -> Result<$struct_name, PolygonalElementError> {
+                let references = parse_references(parameters, $minimum, &[$( TripletFormat::$format, )*])?;
+                Ok($struct_name { references })
+            }
+
+            // Convert every reference to an absolute index. Must be done before more vertex data is read.
+            pub fn resolve(&mut self, counts: &VertexCounts) -> Result<(), IndexError> {
+                for reference in self.references.iter_mut() {
+                    *reference = reference.resolve(counts)?;
+                }
+                Ok(())
+            }
+
+            // Form shared by every reference in the element
+            pub fn format(&self) -> Option<TripletFormat> {
+                self.references.first().map(|reference| reference.format())
+            }
+        }
+    };
+}
+
+polygonal_element!(Point, 1, Vertex);
+polygonal_element!(Line,  2, Vertex, VertexTexture);
+polygonal_element!(Face,  3, Vertex, VertexTexture, VertexNormal, VertexTextureNormal);
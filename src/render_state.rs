@@ -0,0 +1,199 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Display/Render Attributes
+ *      State-setting statements that describe how elements are displayed
+ *          - Bevel Interpolation       "bevel on|off"
+ *          - Color Interpolation       "c_interp on|off"
+ *          - Dissolve Interpolation    "d_interp on|off"
+ *          - Level of Detail           "lod level"
+ *              * 0 - 100, 0 displays every element
+ *          - Texture Map Library       "maplib filename1 filename2 ..."
+ *          - Texture Map               "usemap name|off"
+ *          - Material                  "usemtl name"
+ *          - Material Library          "mtllib filename1 filename2 ..."
+ *          - Shadow Casting Object     "shadow_obj filename"
+ *          - Ray Tracing Object        "trace_obj filename"
+ *          - Curve Approximation       "ctech technique resolution"
+ *              * cparm res | cspace maxlength | curv maxdist maxangle
+ *          - Surface Approximation     "stech technique resolution"
+ *              * cparma ures vres | cparmb uvres | cspace maxlength | curv maxdist maxangle
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+use std::collections::VecDeque;
+
+use crate::keywords;
+use crate::utility;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RenderStateError {
+    InvalidParameters,          // Wrong number of parameters, or they aren't numbers
+    InvalidSwitch,              // Expected "on" or "off"
+    InvalidLevelOfDetail,       // Level of detail must be between 0 and 100
+    UnknownTechnique,           // Approximation technique isn't part of the specification
+    InvalidKeyword              // Keyword isn't a display/render attribute
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum CurveApproximation {
+    ConstantParametric { resolution: f32 },                     // cparm
+    ConstantSpatial    { max_length: f32 },                     // cspace
+    CurvatureDependent { max_distance: f32, max_angle: f32 }    // curv
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum SurfaceApproximation {
+    ConstantParametric       { u_resolution: f32, v_resolution: f32 },  // cparma
+    ConstantParametricSingle { resolution: f32 },                       // cparmb
+    ConstantSpatial          { max_length: f32 },                       // cspace
+    CurvatureDependent       { max_distance: f32, max_angle: f32 }      // curv
+}
+
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct RenderState {
+    pub bevel:                  bool,
+    pub color_interpolation:    bool,
+    pub dissolve_interpolation: bool,
+    pub level_of_detail:        u32,
+    pub map_libraries:          Vec<String>,
+    pub texture_map:            Option<String>,         // None when "usemap off"
+    pub material:               Option<String>,
+    pub material_libraries:     Vec<String>,
+    pub shadow_object:          Option<String>,
+    pub trace_object:           Option<String>,
+    pub curve_approximation:    Option<CurveApproximation>,
+    pub surface_approximation:  Option<SurfaceApproximation>
+}
+
+impl RenderState {
+    pub fn new() -> RenderState {
+        RenderState::default()
+    }
+
+    pub fn apply(&mut self, keyword: &str, parameters: &VecDeque<String>) -> Result<(), RenderStateError> {
+        match keyword {
+            keywords::BEVEL_INTERPOLATION    => self.bevel = RenderState::parse_switch(parameters)?,
+            keywords::COLOR_INTERPOLATION    => self.color_interpolation = RenderState::parse_switch(parameters)?,
+            keywords::DISSOLVE_INTERPOLATION => self.dissolve_interpolation = RenderState::parse_switch(parameters)?,
+            keywords::LEVEL_OF_DETAIL        => {
+                let level = RenderState::single(parameters)?.parse::<u32>().ok().ok_or(RenderStateError::InvalidLevelOfDetail)?;
+                if level > 100 {
+                    return Err(RenderStateError::InvalidLevelOfDetail);
+                }
+                self.level_of_detail = level;
+            }
+            keywords::MAP_LIB                => self.map_libraries = RenderState::file_names(parameters)?,
+            keywords::USE_MAP                => {
+                let map = RenderState::single(parameters)?;
+                self.texture_map = if map == keywords::STATE_OFF { None } else { Some(map.to_owned()) };
+            }
+            keywords::MATERIAL_NAME          => self.material = Some(RenderState::single(parameters)?.to_owned()),
+            keywords::MATERIAL_LIBRARY       => self.material_libraries = RenderState::file_names(parameters)?,
+            keywords::SHADOW_CASTING         => self.shadow_object = Some(RenderState::single(parameters)?.to_owned()),
+            keywords::RAY_TRACING            => self.trace_object = Some(RenderState::single(parameters)?.to_owned()),
+            keywords::CURVE_APPROXIMATION    => self.curve_approximation = Some(CurveApproximation::from(parameters)?),
+            keywords::SURFACE_APPROXIMATION  => self.surface_approximation = Some(SurfaceApproximation::from(parameters)?),
+            _                                => return Err(RenderStateError::InvalidKeyword)
+        }
+        Ok(())
+    }
+
+    fn parse_switch(parameters: &VecDeque<String>) -> Result<bool, RenderStateError> {
+        match RenderState::single(parameters)? {
+            keywords::STATE_ON  => Ok(true),
+            keywords::STATE_OFF => Ok(false),
+            _                   => Err(RenderStateError::InvalidSwitch)
+        }
+    }
+
+    fn single(parameters: &VecDeque<String>) -> Result<&str, RenderStateError> {
+        if parameters.len() != 1 {
+            return Err(RenderStateError::InvalidParameters);
+        }
+        Ok(&parameters[0])
+    }
+
+    fn file_names(parameters: &VecDeque<String>) -> Result<Vec<String>, RenderStateError> {
+        if parameters.is_empty() {
+            return Err(RenderStateError::InvalidParameters);
+        }
+        Ok(parameters.iter().cloned().collect())
+    }
+}
+
+// Technique followed by its numeric parameters
+fn technique(parameters: &VecDeque<String>) -> Result<(&str, Vec<f32>), RenderStateError> {
+    let mut values = parameters.clone();
+    let technique = parameters.front().ok_or(RenderStateError::InvalidParameters)?;
+    values.pop_front();
+    Ok((technique, utility::convert_vec::<f32>(&values).ok().ok_or(RenderStateError::InvalidParameters)?))
+}
+
+impl CurveApproximation {
+    pub fn from(parameters: &VecDeque<String>) -> Result<CurveApproximation, RenderStateError> {
+        let (technique, values) = technique(parameters)?;
+        match (technique, values.as_slice()) {
+            (keywords::CONSTANT_PARAMETRIC_SUBDIVISION, [resolution])          => Ok(CurveApproximation::ConstantParametric { resolution: *resolution }),
+            (keywords::CONSTANT_SPATIAL_SUBDIVISION, [max_length])             => Ok(CurveApproximation::ConstantSpatial { max_length: *max_length }),
+            (keywords::CURVE_DEPENDANT_SUBDIVISION, [max_distance, max_angle]) => Ok(CurveApproximation::CurvatureDependent { max_distance: *max_distance, max_angle: *max_angle }),
+            (keywords::CONSTANT_PARAMETRIC_SUBDIVISION, _) |
+            (keywords::CONSTANT_SPATIAL_SUBDIVISION, _)    |
+            (keywords::CURVE_DEPENDANT_SUBDIVISION, _)                         => Err(RenderStateError::InvalidParameters),
+            _                                                                  => Err(RenderStateError::UnknownTechnique)
+        }
+    }
+
+    pub fn technique(&self) -> &'static str {
+        match self {
+            CurveApproximation::ConstantParametric { .. } => keywords::CONSTANT_PARAMETRIC_SUBDIVISION,
+            CurveApproximation::ConstantSpatial { .. }    => keywords::CONSTANT_SPATIAL_SUBDIVISION,
+            CurveApproximation::CurvatureDependent { .. } => keywords::CURVE_DEPENDANT_SUBDIVISION
+        }
+    }
+
+    pub fn parameters(&self) -> Vec<f32> {
+        match *self {
+            CurveApproximation::ConstantParametric { resolution }              => vec![resolution],
+            CurveApproximation::ConstantSpatial { max_length }                 => vec![max_length],
+            CurveApproximation::CurvatureDependent { max_distance, max_angle } => vec![max_distance, max_angle]
+        }
+    }
+}
+
+impl SurfaceApproximation {
+    pub fn from(parameters: &VecDeque<String>) -> Result<SurfaceApproximation, RenderStateError> {
+        let (technique, values) = technique(parameters)?;
+        match (technique, values.as_slice()) {
+            (keywords::CONSTANT_PARAMETRIC_SUBDIVISION_SURFACE_MULTI, [u_resolution, v_resolution]) => Ok(SurfaceApproximation::ConstantParametric { u_resolution: *u_resolution, v_resolution: *v_resolution }),
+            (keywords::CONSTANT_PARAMETRIC_SUBDIVISION_SURFACE_SINGLE, [resolution])                => Ok(SurfaceApproximation::ConstantParametricSingle { resolution: *resolution }),
+            (keywords::CONSTANT_SPATIAL_SUBDIVISION, [max_length])                                  => Ok(SurfaceApproximation::ConstantSpatial { max_length: *max_length }),
+            (keywords::CURVE_DEPENDANT_SUBDIVISION, [max_distance, max_angle])                      => Ok(SurfaceApproximation::CurvatureDependent { max_distance: *max_distance, max_angle: *max_angle }),
+            (keywords::CONSTANT_PARAMETRIC_SUBDIVISION_SURFACE_MULTI, _)  |
+            (keywords::CONSTANT_PARAMETRIC_SUBDIVISION_SURFACE_SINGLE, _) |
+            (keywords::CONSTANT_SPATIAL_SUBDIVISION, _)                   |
+            (keywords::CURVE_DEPENDANT_SUBDIVISION, _)                                              => Err(RenderStateError::InvalidParameters),
+            _                                                                                       => Err(RenderStateError::UnknownTechnique)
+        }
+    }
+
+    pub fn technique(&self) -> &'static str {
+        match self {
+            SurfaceApproximation::ConstantParametric { .. }       => keywords::CONSTANT_PARAMETRIC_SUBDIVISION_SURFACE_MULTI,
+            SurfaceApproximation::ConstantParametricSingle { .. } => keywords::CONSTANT_PARAMETRIC_SUBDIVISION_SURFACE_SINGLE,
+            SurfaceApproximation::ConstantSpatial { .. }          => keywords::CONSTANT_SPATIAL_SUBDIVISION,
+            SurfaceApproximation::CurvatureDependent { .. }       => keywords::CURVE_DEPENDANT_SUBDIVISION
+        }
+    }
+
+    pub fn parameters(&self) -> Vec<f32> {
+        match *self {
+            SurfaceApproximation::ConstantParametric { u_resolution, v_resolution } => vec![u_resolution, v_resolution],
+            SurfaceApproximation::ConstantParametricSingle { resolution }           => vec![resolution],
+            SurfaceApproximation::ConstantSpatial { max_length }                    => vec![max_length],
+            SurfaceApproximation::CurvatureDependent { max_distance, max_angle }    => vec![max_distance, max_angle]
+        }
+    }
+}
@@ -0,0 +1,63 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ * 
+ * Source Spans
+ *      Where a statement came from in the source.
+ *      Lines and columns start at 1. A statement can span multiple lines when "\" is used.
+ *      
+ *      Errors are wrapped with the span of the statement that caused them.
+ * 
+ * ------------------------------------------------------------------------------------*/
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Span {
+    pub start_line:   usize,    // Line the statement starts on
+    pub end_line:     usize,    // Last line of the statement, includes continuations
+    pub start_column: usize,    // Column of the first character on the starting line
+    pub end_column:   usize     // Column after the last character on the ending line
+}
+
+impl Span {
+    pub fn new(start_line: usize, end_line: usize, start_column: usize, end_column: usize) -> Span {
+        Span { start_line, end_line, start_column, end_column }
+    }
+
+    // Span covering a single line
+    pub fn line(line: usize) -> Span {
+        Span { start_line: line, end_line: line, start_column: 1, end_column: 1 }
+    }
+
+    pub fn is_multiline(&self) -> bool {
+        self.start_line != self.end_line
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Spanned<E> {
+    pub error: E,
+    pub span:  Span
+}
+
+impl<E> Spanned<E> {
+    pub fn new(error: E, span: Span) -> Spanned<E> {
+        Spanned { error, span }
+    }
+
+    // Convert the wrapped error, keeping the span
+    pub fn map<F>(self, convert: impl FnOnce(E) -> F) -> Spanned<F> {
+        Spanned { error: convert(self.error), span: self.span }
+    }
+}
+
+// Attach a span to the error of a result
+pub trait WithSpan<T, E> {
+    fn with_span(self, span: Span) -> Result<T, Spanned<E>>;
+}
+
+impl<T, E> WithSpan<T, E> for Result<T, E> {
+    fn with_span(self, span: Span) -> Result<T, Spanned<E>> {
+        self.map_err(|error| Spanned::new(error, span))
+    }
+}
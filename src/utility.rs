@@ -1,16 +1,16 @@
 use std::collections::VecDeque;
 use std::str::FromStr;
 
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum UtilityError {
     ConversionError
 }
 
 pub fn convert_vec<DataT: FromStr>(buffer: &VecDeque<String>) -> Result<Vec<DataT>, UtilityError> {
-    let mut converted_parameters = Vec::<DataT>::new();
-    converted_parameters.reserve(buffer.len());
+    let mut converted_parameters = Vec::<DataT>::with_capacity(buffer.len());
 
-    for i in 0..buffer.len() {
-        if let Ok(new_parameter) = buffer[i].parse::<DataT>() {
+    for parameter in buffer {
+        if let Ok(new_parameter) = parameter.parse::<DataT>() {
             converted_parameters.push(new_parameter);
         } else {
             return Err(UtilityError::ConversionError);
@@ -18,4 +18,116 @@ pub fn convert_vec<DataT: FromStr>(buffer: &VecDeque<String>) -> Result<Vec<Data
     }
 
     Ok(converted_parameters)
-}
\ No newline at end of file
+}
+
+/* --------------------------------------------------------------------------------------
+ * Numeric Fast Path
+ *      Most numbers in an OBJ file are short decimals. ex: "-0.125", "12", "1.5e-3"
+ *      Those are parsed directly. Anything the fast path can't represent exactly
+ *      (long mantissas, large exponents, inf, nan, etc) falls back to str::parse,
+ *      so results always match str::parse.
+ * ------------------------------------------------------------------------------------*/
+
+// Powers of ten that are exact in an f32
+const POWERS_OF_TEN: [f32; 11] = [1e0, 1e1, 1e2, 1e3, 1e4, 1e5, 1e6, 1e7, 1e8, 1e9, 1e10];
+
+// Largest mantissa an f32 holds exactly
+const MAX_EXACT_MANTISSA: u64 = 1 << 24;
+
+pub fn parse_f32(text: &str) -> Option<f32> {
+    parse_f32_fast(text).or_else(|| text.parse().ok())
+}
+
+fn parse_f32_fast(text: &str) -> Option<f32> {
+    let bytes = text.as_bytes();
+    let (negative, mut position) = match bytes.first()? {
+        b'-' => (true, 1),
+        b'+' => (false, 1),
+        _    => (false, 0)
+    };
+
+    let mut mantissa: u64 = 0;
+    let mut exponent: i32 = 0;
+    let mut digits:   usize = 0;
+
+    while let Some(digit) = bytes.get(position).filter(|byte| byte.is_ascii_digit()) {
+        mantissa = mantissa * 10 + (digit - b'0') as u64;
+        digits += 1;
+        position += 1;
+        if mantissa > MAX_EXACT_MANTISSA {
+            return None;
+        }
+    }
+
+    if bytes.get(position) == Some(&b'.') {
+        position += 1;
+        while let Some(digit) = bytes.get(position).filter(|byte| byte.is_ascii_digit()) {
+            mantissa = mantissa * 10 + (digit - b'0') as u64;
+            exponent -= 1;
+            digits += 1;
+            position += 1;
+            if mantissa > MAX_EXACT_MANTISSA {
+                return None;
+            }
+        }
+    }
+
+    if digits == 0 {
+        return None;
+    }
+
+    if let Some(b'e' | b'E') = bytes.get(position) {
+        position += 1;
+        let (negative, start) = match bytes.get(position) {
+            Some(b'-') => (true, position + 1),
+            Some(b'+') => (false, position + 1),
+            _          => (false, position)
+        };
+        position = start;
+
+        let mut value: i32 = 0;
+        while let Some(digit) = bytes.get(position).filter(|byte| byte.is_ascii_digit()) {
+            value = value.checked_mul(10)?.checked_add((digit - b'0') as i32)?;
+            position += 1;
+        }
+        if position == start {
+            return None;
+        }
+        exponent += if negative { -value } else { value };
+    }
+
+    // Trailing characters aren't part of the number
+    if position != bytes.len() {
+        return None;
+    }
+
+    // Both the mantissa and the power of ten are exact, so one operation rounds correctly
+    let power = *POWERS_OF_TEN.get(exponent.unsigned_abs() as usize)?;
+    let value = if exponent < 0 { mantissa as f32 / power } else { mantissa as f32 * power };
+    Some(if negative { -value } else { value })
+}
+
+// Integers such as element references. Overflow is reported as None.
+pub fn parse_isize(text: &str) -> Option<isize> {
+    let bytes = text.as_bytes();
+    let (negative, digits) = match bytes.first()? {
+        b'-' => (true, &bytes[1..]),
+        b'+' => (false, &bytes[1..]),
+        _    => (false, bytes)
+    };
+
+    if digits.is_empty() {
+        return None;
+    }
+
+    let mut value: isize = 0;
+    for digit in digits {
+        if !digit.is_ascii_digit() {
+            return None;
+        }
+        // Accumulate negatively so isize::MIN is representable
+        value = value.checked_mul(10)?.checked_sub((digit - b'0') as isize)?;
+    }
+
+    if negative { Some(value) } else { value.checked_neg() }
+}
@@ -16,13 +16,22 @@ use crate::index::Index;
 
 type BufferObject<T> = Vec<Rc<T>>;
 
-#[derive(Debug)]
+#[derive(Debug, Clone, Copy, PartialEq)]
 pub enum VertexBufferError {
     InterpreterError,
     BoundsException
 }
 
-#[derive(Debug, Clone, Default)]
+// Size of each buffer. Relative indices are resolved against these
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct VertexCounts {
+    pub vertices:                 usize,
+    pub texture_coordinates:      usize,
+    pub normals:                  usize,
+    pub parameter_space_vertices: usize
+}
+
+#[derive(Debug, Clone, Default, PartialEq)]
 pub struct VertexBuffer {
     vertices:                 BufferObject<Vertex>,
     texture_coordinates:      BufferObject<TextureCoordinate>,
@@ -77,6 +86,28 @@ impl VertexBuffer {
         Err(VertexBufferError::InterpreterError)
     }
 
+    // Fast path. Parses the parameters straight from the line text. (ex: ObjLineRef::parameters)
+    pub fn parse_vertex(&mut self, parameters: &str) -> Result<(), VertexBufferError> {
+        let vertex = Vertex::parse(parameters).ok_or(VertexBufferError::InterpreterError)?;
+        self.vertices.push(Rc::new(vertex));
+        Ok(())
+    }
+    pub fn parse_texture_coordinate(&mut self, parameters: &str) -> Result<(), VertexBufferError> {
+        let texture_coordinate = TextureCoordinate::parse(parameters).ok_or(VertexBufferError::InterpreterError)?;
+        self.texture_coordinates.push(Rc::new(texture_coordinate));
+        Ok(())
+    }
+    pub fn parse_normal(&mut self, parameters: &str) -> Result<(), VertexBufferError> {
+        let normal = VertexNormal::parse(parameters).ok_or(VertexBufferError::InterpreterError)?;
+        self.normals.push(Rc::new(normal));
+        Ok(())
+    }
+    pub fn parse_parameter_space_vertex(&mut self, parameters: &str) -> Result<(), VertexBufferError> {
+        let parameter_space_vertex = ParameterSpaceVertex::parse(parameters).ok_or(VertexBufferError::InterpreterError)?;
+        self.parameter_space_vertices.push(Rc::new(parameter_space_vertex));
+        Ok(())
+    }
+
     pub fn get_vertex(&self, index: Index) -> Result<Rc<Vertex>, VertexBufferError> {
         VertexBuffer::get_from_buffer(&self.vertices, index)       
     }
@@ -90,23 +121,42 @@ impl VertexBuffer {
         VertexBuffer::get_from_buffer(&self.parameter_space_vertices, index)       
     }
 
-    fn get_from_buffer<ContentType>(buffer: &BufferObject<ContentType>, index: Index) -> Result<Rc<ContentType>, VertexBufferError> {
-        let index = index.as_isize();
-        if index < 0 {
-            // If negative, the index is relative to the end of the buffer
-            let index = index.abs() as usize;
-            if let Some(value) = buffer.get(buffer.len() - index) {
-                Ok(value.clone())
-            } else {
-                Err(VertexBufferError::BoundsException)
-            }
-        } else {
-            // If positive, absolute position
-            if let Some(value) = buffer.get(index as usize) {
-                Ok(value.clone())
-            } else {
-                Err(VertexBufferError::BoundsException)
-            }
+    pub fn vertices(&self) -> &[Rc<Vertex>] {
+        &self.vertices
+    }
+    pub fn texture_coordinates(&self) -> &[Rc<TextureCoordinate>] {
+        &self.texture_coordinates
+    }
+    pub fn normals(&self) -> &[Rc<VertexNormal>] {
+        &self.normals
+    }
+    pub fn parameter_space_vertices(&self) -> &[Rc<ParameterSpaceVertex>] {
+        &self.parameter_space_vertices
+    }
+
+    pub fn counts(&self) -> VertexCounts {
+        VertexCounts {
+            vertices:                 self.vertices.len(),
+            texture_coordinates:      self.texture_coordinates.len(),
+            normals:                  self.normals.len(),
+            parameter_space_vertices: self.parameter_space_vertices.len()
         }
     }
+    pub fn vertex_count(&self) -> usize {
+        self.vertices.len()
+    }
+    pub fn texture_coordinate_count(&self) -> usize {
+        self.texture_coordinates.len()
+    }
+    pub fn normal_count(&self) -> usize {
+        self.normals.len()
+    }
+    pub fn parameter_space_vertex_count(&self) -> usize {
+        self.parameter_space_vertices.len()
+    }
+
+    fn get_from_buffer<ContentType>(buffer: &BufferObject<ContentType>, index: Index) -> Result<Rc<ContentType>, VertexBufferError> {
+        let position = index.resolve(buffer.len()).ok().ok_or(VertexBufferError::BoundsException)?;
+        Ok(buffer[position].clone())
+    }
 }
\ No newline at end of file
@@ -9,10 +9,7 @@
 
 use std::collections::VecDeque;
 
-macro_rules! count {
-    () => (0usize);
-    ( $x:tt $($xs:tt)* ) => (1usize + count!($($xs)*));
-}
+use crate::utility;
 
 // Generalized Vertex data. Could be a derive, but I dont' feel like it for something this small
 macro_rules! vertex_data {
@@ -37,55 +34,57 @@ macro_rules! vertex_data {
     };
 }
 
-// Generalized from method. Could be a derive, but I don't feel like it for something this small
+// Generalized from methods. Both parse the components without collecting them first.
 macro_rules! vertex_from {
-    ( $struct_name:ident, $ty:ty, $( $v:ident ),* ) => {
-        #[allow(unused_assignments)]
+    ( $struct_name:ident ) => {
         pub fn from(elements: &VecDeque<String>) -> Option<$struct_name> {
-            // Validate the number of elements read from the line
-            if elements.len() < count!($($v)*) {
-                return None;
-            }
-
-            // convert the elements into the correct type
-            let mut converted_elements = Vec::<$ty>::new();
-            converted_elements.reserve(elements.len());
-            for element in elements {
-                converted_elements.push(element.parse().ok()?);
-            }
-
-            // Create variables for struct using short hand naming convention
-            let mut i = 0usize;
-            $(
-                let $v = converted_elements[i];
-                i += 1;
-            )*
+            $struct_name::from_components(elements.iter().map(|element| element.as_str()))
+        }
 
-            // Return the struct
-            Some($struct_name {$($v,)*})
+        // Parse the parameters of a line straight from the text. (ex: ObjLineRef::parameters)
+        pub fn parse(parameters: &str) -> Option<$struct_name> {
+            $struct_name::from_components(parameters.split_ascii_whitespace())
         }
     }
 }
 
+// Parse up to N components into an array. Returns the number read.
+// None if there are more than N components, or one isn't a number.
+fn parse_components<'a, const N: usize>(components: impl Iterator<Item = &'a str>) -> Option<([f32; N], usize)> {
+    let mut converted_elements = [0.0f32; N];
+    let mut count = 0usize;
+    for component in components {
+        *converted_elements.get_mut(count)? = utility::parse_f32(component)?;
+        count += 1;
+    }
+    Some((converted_elements, count))
+}
+
 vertex_data!(Vertex, f32, x, y, z, w);
 vertex_data!(ParameterSpaceVertex, f32, u, v, w);
 vertex_data!(VertexNormal, f32, i, j, k);
 vertex_data!(TextureCoordinate, f32, u, v, w);
 
-impl VertexNormal           { vertex_from!(VertexNormal, f32, i, j, k);         }
-impl TextureCoordinate      { vertex_from!(TextureCoordinate, f32, u, v, w);   }
+// Extra components after i j k are ignored, as long as they are numbers.
+impl VertexNormal {
+    vertex_from!(VertexNormal);
+
+    fn from_components<'a>(mut components: impl Iterator<Item = &'a str>) -> Option<VertexNormal> {
+        let (converted_elements, count) = parse_components::<3>(components.by_ref().take(3))?;
+        if count != 3 || !components.all(|component| utility::parse_f32(component).is_some()) {
+            return None;
+        }
+        Some(VertexNormal { i: converted_elements[0], j: converted_elements[1], k: converted_elements[2] })
+    }
+}
 
 // The Vertex "from" method is unique as it has the chance to have a default value.
 impl Vertex {
-    pub fn from(elements: &VecDeque<String>) -> Option<Vertex> {
-        // convert the elements into the correct type
-        let mut converted_elements = Vec::<f32>::new();
-        converted_elements.reserve(elements.len());
-        for element in elements {
-            converted_elements.push(element.parse().ok()?);
-        }
+    vertex_from!(Vertex);
 
-        match converted_elements.len() {
+    fn from_components<'a>(components: impl Iterator<Item = &'a str>) -> Option<Vertex> {
+        let (converted_elements, count) = parse_components::<4>(components)?;
+        match count {
             3 => Some(Vertex { x: converted_elements[0], y: converted_elements[1], z: converted_elements[2], w: 1.0 }),
             4 => Some(Vertex { x: converted_elements[0], y: converted_elements[1], z: converted_elements[2], w: converted_elements[3] }),
             _ => None
@@ -95,18 +94,33 @@ impl Vertex {
 
 // The ParameterSpaceVertex "from" method is unique as it has the chance to have a default value.
 impl ParameterSpaceVertex {
-    pub fn from(elements: &VecDeque<String>) -> Option<ParameterSpaceVertex> {
-        // convert the elements into the correct type
-        let mut converted_elements = Vec::<f32>::new();
-        converted_elements.reserve(elements.len());
-        for element in elements {
-            converted_elements.push(element.parse().ok()?);
-        }
+    vertex_from!(ParameterSpaceVertex);
 
-        match converted_elements.len() {
+    fn from_components<'a>(components: impl Iterator<Item = &'a str>) -> Option<ParameterSpaceVertex> {
+        let (converted_elements, count) = parse_components::<3>(components)?;
+        match count {
             2 => Some(ParameterSpaceVertex { u: converted_elements[0], v: converted_elements[1], w: 1.0 }),
             3 => Some(ParameterSpaceVertex { u: converted_elements[0], v: converted_elements[1], w: converted_elements[2] }),
             _ => None
         }
     }
-}
\ No newline at end of file
+}
+
+// The TextureCoordinate "from" method is unique as v and w are optional, and default to 0.
+// Extra components after u v w are ignored, as long as they are numbers.
+impl TextureCoordinate {
+    vertex_from!(TextureCoordinate);
+
+    fn from_components<'a>(mut components: impl Iterator<Item = &'a str>) -> Option<TextureCoordinate> {
+        let (converted_elements, count) = parse_components::<3>(components.by_ref().take(3))?;
+        if !components.all(|component| utility::parse_f32(component).is_some()) {
+            return None;
+        }
+        match count {
+            1 => Some(TextureCoordinate { u: converted_elements[0], v: 0.0, w: 0.0 }),
+            2 => Some(TextureCoordinate { u: converted_elements[0], v: converted_elements[1], w: 0.0 }),
+            3 => Some(TextureCoordinate { u: converted_elements[0], v: converted_elements[1], w: converted_elements[2] }),
+            _ => None
+        }
+    }
+}
@@ -0,0 +1,128 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ *
+ * OBJ Visitor
+ *      Streams a file without building a model. (ex: uploading vertices straight to the GPU)
+ *      The parser calls back into the visitor for every statement, in file order.
+ *      Every callback does nothing by default, so visitors only implement what they need.
+ *
+ *      Visitors are driven by the interpreter's state machine, so statements are validated the same way.
+ *      Only the size of each buffer is kept, so relative indices can still be resolved.
+ *      Free-form elements are held until their "end" statement, since they own their body.
+ *
+ * ------------------------------------------------------------------------------------*/
+
+use std::io::BufRead;
+use std::collections::VecDeque;
+
+use crate::parser::{ObjLine, ObjParser};
+use crate::keywords::Keyword;
+use crate::vertex_data::{Vertex, TextureCoordinate, VertexNormal, ParameterSpaceVertex};
+use crate::vertex_buffer::VertexCounts;
+use crate::element::{Element, Geometry};
+use crate::grouping::Grouping;
+use crate::polygonal_geometry::polygonal_elements::{Point, Line, Face};
+use crate::interpreter::{InterpreterError, InterpreterState, InterpreterOutput};
+use crate::options::{ParseOptions, Deviation};
+use crate::span::Spanned;
+use crate::freeform_geometry::connection::Connection;
+
+#[allow(unused_variables)]
+pub trait ObjVisitor {
+    // Vertex data
+    fn vertex(&mut self, vertex: Vertex) {}
+    fn texture_coordinate(&mut self, coordinate: TextureCoordinate) {}
+    fn normal(&mut self, normal: VertexNormal) {}
+    fn parameter_space_vertex(&mut self, vertex: ParameterSpaceVertex) {}
+    // Polygonal elements. Every reference is absolute
+    fn point(&mut self, point: &Point) {}
+    fn line(&mut self, line: &Line) {}
+    fn face(&mut self, face: &Face) {}
+    // Free-form elements, and the statements in their body
+    fn free_form_begin(&mut self, element: &Geometry) {}
+    fn body_statement(&mut self, keyword: &Keyword, parameters: &VecDeque<String>) {}
+    fn end(&mut self, element: &Geometry) {}
+    // State changes
+    fn group(&mut self, grouping: &Grouping) {}                   // g, s, mg, o
+    fn use_material(&mut self, material: &str) {}
+    // Everything else. (ex: free-form attributes, con, mtllib)
+    fn statement(&mut self, line: &ObjLine) {}
+    // Deviation from the specification the options allowed. Called before the statement it was found in
+    fn warning(&mut self, warning: Spanned<Deviation>) {}
+}
+
+// Sends what the interpreter reads to the visitor. Only the size of each buffer is kept
+struct VisitorOutput<'v, V: ObjVisitor> {
+    visitor: &'v mut V,
+    counts:  VertexCounts
+}
+
+impl<R: BufRead> ObjParser<R> {
+    // Stream every remaining line into the visitor
+    pub fn visit<V: ObjVisitor>(&mut self, visitor: &mut V) -> Result<(), Spanned<InterpreterError>> {
+        self.visit_with_options(visitor, ParseOptions::default())
+    }
+
+    pub fn visit_with_options<V: ObjVisitor>(&mut self, visitor: &mut V, options: ParseOptions) -> Result<(), Spanned<InterpreterError>> {
+        let mut state = InterpreterState::new(options);
+        let mut output = VisitorOutput { visitor, counts: VertexCounts::default() };
+        self.set_latin1_fallback(options.latin1_text);
+        state.interpret_all(self, &mut output)
+    }
+}
+
+impl<V: ObjVisitor> InterpreterOutput for VisitorOutput<'_, V> {
+    fn counts(&self) -> VertexCounts {
+        self.counts
+    }
+    fn vertex(&mut self, vertex: Vertex) {
+        self.counts.vertices += 1;
+        self.visitor.vertex(vertex);
+    }
+    fn texture_coordinate(&mut self, coordinate: TextureCoordinate) {
+        self.counts.texture_coordinates += 1;
+        self.visitor.texture_coordinate(coordinate);
+    }
+    fn normal(&mut self, normal: VertexNormal) {
+        self.counts.normals += 1;
+        self.visitor.normal(normal);
+    }
+    fn parameter_space_vertex(&mut self, vertex: ParameterSpaceVertex) {
+        self.counts.parameter_space_vertices += 1;
+        self.visitor.parameter_space_vertex(vertex);
+    }
+    fn element(&mut self, element: Element) {
+        match &element.geometry {
+            Geometry::Point(point) => self.visitor.point(point),
+            Geometry::Line(line)   => self.visitor.line(line),
+            Geometry::Face(face)   => self.visitor.face(face),
+            geometry               => self.visitor.end(geometry)
+        }
+    }
+    fn free_form_begin(&mut self, element: &Element) {
+        self.visitor.free_form_begin(&element.geometry);
+    }
+    fn body_statement(&mut self, keyword: &Keyword, parameters: &VecDeque<String>) {
+        self.visitor.body_statement(keyword, parameters);
+    }
+    fn group(&mut self, grouping: &Grouping) {
+        self.visitor.group(grouping);
+    }
+    fn use_material(&mut self, material: &str) {
+        self.visitor.use_material(material);
+    }
+    fn material_library(&mut self, line: ObjLine) {
+        self.visitor.statement(&line);
+    }
+    fn connection(&mut self, _connection: Connection, line: ObjLine) {
+        self.visitor.statement(&line);
+    }
+    fn statement(&mut self, line: ObjLine) {
+        self.visitor.statement(&line);
+    }
+    fn warning(&mut self, warning: Spanned<Deviation>) {
+        self.visitor.warning(warning);
+    }
+}
@@ -0,0 +1,343 @@
+/* --------------------------------------------------------------------------------------
+
+ * Author: agent
+ * Last Edit: agent (10/18/2026)
+ *
+ * OBJ Writer
+ *      Serializes an ObjModel back into an OBJ file.
+ *
+ *      Vertex data is written first. Elements follow in the order they were read.
+ *      References are written as absolute indices, so moving the vertex data ahead of
+ *      the elements doesn't change what they point at.
+ *
+ *      State-setting statements are only written when the state changes between elements.
+ *      Material libraries apply to the whole file, and are written once ahead of everything else.
+ *
+ * ------------------------------------------------------------------------------------*/
+
+use std::rc::Rc;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::keywords;
+use crate::model::ObjModel;
+use crate::vertex_buffer::VertexBuffer;
+use crate::element::{Element, Geometry};
+use crate::grouping::Grouping;
+use crate::render_state::RenderState;
+use crate::index::Index;
+use crate::polygonal_geometry::index_triplet::IndexTriplet;
+use crate::freeform_geometry::freeform_definition::FreeFormDefinition;
+use crate::freeform_geometry::freeform_types::FreeFormType;
+use crate::freeform_geometry::freeform_elements::{Curve, Curve2D, Surface};
+use crate::freeform_geometry::body_statements::{FreeFormBody, CurveReference};
+use crate::freeform_geometry::basis_matrix::matrix::Matrix;
+use crate::freeform_geometry::uv_pair::UVPair;
+
+pub struct ObjWriter<W: Write> {
+    writer:       W,
+    grouping:     Rc<Grouping>,                 // Last grouping state written
+    render_state: Rc<RenderState>,              // Last display/render state written
+    definition:   Option<Rc<FreeFormDefinition>> // Last free-form definition written
+}
+
+impl ObjWriter<BufWriter<File>> {
+    // Create a file to write to. Overwrites any existing file.
+    pub fn create(path: &str) -> std::io::Result<ObjWriter<BufWriter<File>>> {
+        Ok(ObjWriter::new(BufWriter::new(File::create(path)?)))
+    }
+
+    // Write a model to a file.
+    pub fn save(model: &ObjModel, path: &str) -> std::io::Result<()> {
+        let mut writer = ObjWriter::create(path)?;
+        writer.write_model(model)?;
+        writer.flush()
+    }
+}
+
+impl<W: Write> ObjWriter<W> {
+    pub fn new(writer: W) -> ObjWriter<W> {
+        ObjWriter { writer, grouping: Rc::default(), render_state: Rc::default(), definition: None }
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn write_model(&mut self, model: &ObjModel) -> std::io::Result<()> {
+        if !model.material_libraries.is_empty() {
+            writeln!(self.writer, "{} {}", keywords::MATERIAL_LIBRARY, model.material_libraries.join(" "))?;
+        }
+        self.write_vertex_buffer(&model.vertex_buffer)?;
+
+        for element in &model.elements {
+            self.write_element(element)?;
+        }
+
+        for connection in &model.connections {
+            writeln!(self.writer, "{} {connection}", keywords::CONNECTION)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_vertex_buffer(&mut self, buffer: &VertexBuffer) -> std::io::Result<()> {
+        // Optional components are only written when they differ from their defaults
+        for vertex in buffer.vertices() {
+            write!(self.writer, "{} {} {} {}", keywords::VERTEX, vertex.x, vertex.y, vertex.z)?;
+            if vertex.w != 1.0 {
+                write!(self.writer, " {}", vertex.w)?;
+            }
+            writeln!(self.writer)?;
+        }
+
+        for coordinate in buffer.texture_coordinates() {
+            write!(self.writer, "{} {} {}", keywords::TEXTURE_COORDINATE, coordinate.u, coordinate.v)?;
+            if coordinate.w != 0.0 {
+                write!(self.writer, " {}", coordinate.w)?;
+            }
+            writeln!(self.writer)?;
+        }
+
+        for normal in buffer.normals() {
+            writeln!(self.writer, "{} {} {} {}", keywords::VERTEX_NORMAL, normal.i, normal.j, normal.k)?;
+        }
+
+        for vertex in buffer.parameter_space_vertices() {
+            write!(self.writer, "{} {} {}", keywords::PARAMETER_SPACE_VERTEX, vertex.u, vertex.v)?;
+            if vertex.w != 1.0 {
+                write!(self.writer, " {}", vertex.w)?;
+            }
+            writeln!(self.writer)?;
+        }
+        Ok(())
+    }
+
+    pub fn write_element(&mut self, element: &Element) -> std::io::Result<()> {
+        if !Rc::ptr_eq(&self.grouping, &element.grouping) {
+            ObjWriter::write_grouping_changes(&mut self.writer, &self.grouping, &element.grouping)?;
+            self.grouping = element.grouping.clone();
+        }
+
+        if !Rc::ptr_eq(&self.render_state, &element.render_state) {
+            ObjWriter::write_render_state_changes(&mut self.writer, &self.render_state, &element.render_state)?;
+            self.render_state = element.render_state.clone();
+        }
+
+        match &element.geometry {
+            Geometry::Point(point)     => self.write_references(keywords::POINT, &point.references),
+            Geometry::Line(line)       => self.write_references(keywords::LINE,  &line.references),
+            Geometry::Face(face)       => self.write_references(keywords::FACE,  &face.references),
+            Geometry::Curve(curve)     => self.write_curve(curve),
+            Geometry::Curve2D(curve)   => self.write_curve_2d(curve),
+            Geometry::Surface(surface) => self.write_surface(surface)
+        }
+    }
+
+    pub fn write_free_form_definition(&mut self, definition: &FreeFormDefinition) -> std::io::Result<()> {
+        write!(self.writer, "{}", keywords::CURVE_SURFACE_TYPE)?;
+        if definition.rational {
+            write!(self.writer, " {}", keywords::RATIONAL_CURVE)?;
+        }
+        writeln!(self.writer, " {}", definition.form_type.keyword())?;
+
+        ObjWriter::write_uv_pair(&mut self.writer, keywords::DEGREE, &definition.degree)?;
+
+        if let FreeFormType::BasisMatrix(attributes) = &definition.form_type {
+            ObjWriter::write_uv_pair(&mut self.writer, keywords::STEP_SIZE, &attributes.step)?;
+
+            let (u, v) = match &attributes.matrix {
+                Matrix::Curve(u)      => (u, None),
+                Matrix::Surface(u, v) => (u, Some(v))
+            };
+            ObjWriter::write_values(&mut self.writer, &[keywords::BASIS_MATRIX, keywords::BASIS_MATRIX_U], u.as_slice())?;
+            if let Some(v) = v {
+                ObjWriter::write_values(&mut self.writer, &[keywords::BASIS_MATRIX, keywords::BASIS_MATRIX_V], v.as_slice())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_curve(&mut self, curve: &Curve) -> std::io::Result<()> {
+        self.write_definition_change(&curve.definition)?;
+
+        write!(self.writer, "{} {} {}", keywords::CURVE, curve.start, curve.end)?;
+        ObjWriter::write_indices(&mut self.writer, &curve.vertices)?;
+        self.write_body(&curve.body)
+    }
+
+    fn write_curve_2d(&mut self, curve: &Curve2D) -> std::io::Result<()> {
+        self.write_definition_change(&curve.definition)?;
+
+        write!(self.writer, "{}", keywords::CURVE_2D)?;
+        ObjWriter::write_indices(&mut self.writer, &curve.vertices)?;
+        self.write_body(&curve.body)
+    }
+
+    fn write_surface(&mut self, surface: &Surface) -> std::io::Result<()> {
+        self.write_definition_change(&surface.definition)?;
+
+        let ((s0, s1), (t0, t1)) = (surface.s_range, surface.t_range);
+        write!(self.writer, "{} {s0} {s1} {t0} {t1}", keywords::SURFACE)?;
+        for reference in &surface.references {
+            write!(self.writer, " {reference}")?;
+        }
+        writeln!(self.writer)?;
+        self.write_body(&surface.body)
+    }
+
+    // Definitions are shared between elements, only write it when it changes.
+    fn write_definition_change(&mut self, definition: &Rc<FreeFormDefinition>) -> std::io::Result<()> {
+        let changed = match &self.definition {
+            Some(previous) => !Rc::ptr_eq(previous, definition) && **previous != **definition,
+            None           => true
+        };
+        if changed {
+            self.write_free_form_definition(definition)?;
+        }
+        self.definition = Some(definition.clone());
+        Ok(())
+    }
+
+    fn write_body(&mut self, body: &FreeFormBody) -> std::io::Result<()> {
+        if !body.parameters_u.is_empty() {
+            ObjWriter::write_values(&mut self.writer, &[keywords::PARAMETER_VALUE, keywords::BASIS_MATRIX_U], &body.parameters_u)?;
+        }
+        if !body.parameters_v.is_empty() {
+            ObjWriter::write_values(&mut self.writer, &[keywords::PARAMETER_VALUE, keywords::BASIS_MATRIX_V], &body.parameters_v)?;
+        }
+
+        for trim_loop in &body.trim_loops {
+            ObjWriter::write_curve_references(&mut self.writer, keywords::OUTER_TRIM_LOOP, trim_loop)?;
+        }
+        for hole in &body.holes {
+            ObjWriter::write_curve_references(&mut self.writer, keywords::INNER_TRIM_LOOP, hole)?;
+        }
+        for curve in &body.special_curves {
+            ObjWriter::write_curve_references(&mut self.writer, keywords::SPECIAL_CURVE, curve)?;
+        }
+
+        if !body.special_points.is_empty() {
+            write!(self.writer, "{}", keywords::SPECIAL_POINT)?;
+            ObjWriter::write_indices(&mut self.writer, &body.special_points)?;
+        }
+        writeln!(self.writer, "{}", keywords::END)
+    }
+
+    fn write_curve_references(writer: &mut W, keyword: &str, references: &[CurveReference]) -> std::io::Result<()> {
+        write!(writer, "{keyword}")?;
+        for reference in references {
+            write!(writer, " {} {} {}", reference.start, reference.end, reference.curve)?;
+        }
+        writeln!(writer)
+    }
+
+    fn write_indices(writer: &mut W, indices: &[Index]) -> std::io::Result<()> {
+        for index in indices {
+            write!(writer, " {index}")?;
+        }
+        writeln!(writer)
+    }
+
+    fn write_references(&mut self, keyword: &str, references: &[IndexTriplet]) -> std::io::Result<()> {
+        write!(self.writer, "{keyword}")?;
+        for reference in references {
+            write!(self.writer, " {reference}")?;
+        }
+        writeln!(self.writer)
+    }
+
+    fn write_grouping_changes(writer: &mut W, previous: &Grouping, current: &Grouping) -> std::io::Result<()> {
+        if previous.groups != current.groups {
+            writeln!(writer, "{} {}", keywords::GROUP_NAME, current.groups.join(" "))?;
+        }
+
+        if previous.object != current.object {
+            if let Some(object) = &current.object {
+                writeln!(writer, "{} {object}", keywords::OBJECT_NAME)?;
+            }
+        }
+
+        if previous.smoothing_group != current.smoothing_group {
+            match current.smoothing_group {
+                Some(group) => writeln!(writer, "{} {group}", keywords::SMOOTHING_GROUP)?,
+                None        => writeln!(writer, "{} {}", keywords::SMOOTHING_GROUP, keywords::STATE_OFF)?
+            }
+        }
+
+        if previous.merging_group != current.merging_group {
+            match current.merging_group {
+                Some(merging) => writeln!(writer, "{} {} {}", keywords::MERGING_GROUP, merging.group, merging.resolution)?,
+                None          => writeln!(writer, "{} {}", keywords::MERGING_GROUP, keywords::STATE_OFF)?
+            }
+        }
+        Ok(())
+    }
+
+    fn write_render_state_changes(writer: &mut W, previous: &RenderState, current: &RenderState) -> std::io::Result<()> {
+        let switch = |on: bool| if on { keywords::STATE_ON } else { keywords::STATE_OFF };
+
+        if previous.bevel != current.bevel {
+            writeln!(writer, "{} {}", keywords::BEVEL_INTERPOLATION, switch(current.bevel))?;
+        }
+        if previous.color_interpolation != current.color_interpolation {
+            writeln!(writer, "{} {}", keywords::COLOR_INTERPOLATION, switch(current.color_interpolation))?;
+        }
+        if previous.dissolve_interpolation != current.dissolve_interpolation {
+            writeln!(writer, "{} {}", keywords::DISSOLVE_INTERPOLATION, switch(current.dissolve_interpolation))?;
+        }
+        if previous.level_of_detail != current.level_of_detail {
+            writeln!(writer, "{} {}", keywords::LEVEL_OF_DETAIL, current.level_of_detail)?;
+        }
+
+        if previous.map_libraries != current.map_libraries {
+            writeln!(writer, "{} {}", keywords::MAP_LIB, current.map_libraries.join(" "))?;
+        }
+        if previous.texture_map != current.texture_map {
+            writeln!(writer, "{} {}", keywords::USE_MAP, current.texture_map.as_deref().unwrap_or(keywords::STATE_OFF))?;
+        }
+
+        // Once set, these can only be replaced
+        for (keyword, previous, current) in [
+            (keywords::MATERIAL_NAME,  &previous.material,      &current.material),
+            (keywords::SHADOW_CASTING, &previous.shadow_object, &current.shadow_object),
+            (keywords::RAY_TRACING,    &previous.trace_object,  &current.trace_object)
+        ] {
+            if previous != current {
+                if let Some(current) = current {
+                    writeln!(writer, "{keyword} {current}")?;
+                }
+            }
+        }
+
+        if previous.curve_approximation != current.curve_approximation {
+            if let Some(approximation) = &current.curve_approximation {
+                ObjWriter::write_values(writer, &[keywords::CURVE_APPROXIMATION, approximation.technique()], &approximation.parameters())?;
+            }
+        }
+        if previous.surface_approximation != current.surface_approximation {
+            if let Some(approximation) = &current.surface_approximation {
+                ObjWriter::write_values(writer, &[keywords::SURFACE_APPROXIMATION, approximation.technique()], &approximation.parameters())?;
+            }
+        }
+        Ok(())
+    }
+
+    fn write_uv_pair(writer: &mut W, keyword: &str, pair: &UVPair) -> std::io::Result<()> {
+        match pair {
+            UVPair::Curve(u)      => writeln!(writer, "{keyword} {u}"),
+            UVPair::Surface(u, v) => writeln!(writer, "{keyword} {u} {v}")
+        }
+    }
+
+    fn write_values(writer: &mut W, prefix: &[&str], values: &[f32]) -> std::io::Result<()> {
+        write!(writer, "{}", prefix.join(" "))?;
+        for value in values {
+            write!(writer, " {value}")?;
+        }
+        writeln!(writer)
+    }
+}